@@ -0,0 +1,57 @@
+#![feature(test)]
+extern crate test;
+
+use rippled_binary_codec::DefinitionFields;
+use rippled_binary_codec::serialize::{serialize_tx, serialize_tx_value};
+use serde_json::Value;
+use test::{Bencher, black_box};
+
+// a 12-field OfferCreate transaction
+const TX: &str = r#"{
+  "Account": "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys",
+  "Expiration": 595640108,
+  "Fee": "10",
+  "Flags": 524288,
+  "OfferSequence": 1752791,
+  "Sequence": 1752792,
+  "SigningPubKey": "03EE83BB432547885C219634A1BC407A9DB0474145D69737D09CCDC63E1DEE7FE3",
+  "TakerGets": "15000000000",
+  "TakerPays": {"currency": "USD", "issuer": "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B", "value": "7072.8"},
+  "TransactionType": "OfferCreate",
+  "TxnSignature": "30440220143759437C04F7B61F012563AFE90D8DAFC46E86035E1D965A9CED282C97D4CE02204CFD241E86F17E011298FC1A39B63386C74306A5DE047E213B0F29EFA4571C2C",
+  "hash": "73734B611DDA23D3F5F62E20A173B78AB8406AC5015094DA53F53D39B9EDB06C"
+}"#;
+
+// the number of transactions serialized in each iteration of the batch benchmarks
+const BATCH_SIZE: usize = 1000;
+
+#[bench]
+fn bench_serialize_tx(b: &mut Bencher) {
+  b.iter(|| serialize_tx(black_box(TX.to_string()), false, None).unwrap());
+}
+
+#[bench]
+fn bench_serialize_tx_value(b: &mut Bencher) {
+  let tx: Value = serde_json::from_str(TX).unwrap();
+  b.iter(|| serialize_tx_value(black_box(&tx), false, None).unwrap());
+}
+
+#[bench]
+fn bench_serialize_batch_global_definitions(b: &mut Bencher) {
+  b.iter(|| {
+    for _ in 0..BATCH_SIZE {
+      serialize_tx(black_box(TX.to_string()), false, None).unwrap();
+    }
+  });
+}
+
+// parsing definitions.json for every transaction, as `serialize_tx(..., None)` did before `DefinitionFields::global()`
+#[bench]
+fn bench_serialize_batch_new_definitions(b: &mut Bencher) {
+  b.iter(|| {
+    for _ in 0..BATCH_SIZE {
+      let definition_fields = DefinitionFields::new();
+      serialize_tx(black_box(TX.to_string()), false, Some(&definition_fields)).unwrap();
+    }
+  });
+}
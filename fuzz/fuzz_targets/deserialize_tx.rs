@@ -0,0 +1,10 @@
+//! Feeds arbitrary bytes to `deserialize_tx`, which decodes a blob field by field.
+//! It must never panic, only return `None`.
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use rippled_binary_codec::definition_fields::DefinitionFields;
+use rippled_binary_codec::serialize::deserialize_tx;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = deserialize_tx(hex::encode(data), Some(DefinitionFields::global()));
+});
@@ -0,0 +1,10 @@
+//! Feeds arbitrary bytes to `validate_blob_length`, which walks a blob field by field.
+//! It must never panic, only return errors.
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use rippled_binary_codec::definition_fields::DefinitionFields;
+use rippled_binary_codec::serialize::validate_blob_length;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = validate_blob_length(&hex::encode(data), Some(DefinitionFields::global()));
+});
@@ -0,0 +1,11 @@
+//! Feeds arbitrary bytes to `vl_decode`. It must never panic, and whatever it decodes must
+//! encode back to exactly the bytes it consumed.
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use rippled_binary_codec::types::account::{vl_decode, vl_encode};
+
+fuzz_target!(|data: &[u8]| {
+    if let Some((content, consumed)) = vl_decode(data) {
+        assert_eq!(vl_encode(content).as_deref(), Some(&data[..consumed]));
+    }
+});
@@ -1,23 +1,101 @@
 //! A `DefinitionFields` structure to represent the [`definitions.json`](https://github.com/KeystoneHQ/rippled_binary_codec/blob/main/src/fixtures/definitions.json) JSON data and methods to manipulate the fields.
 
-use core::convert::TryInto;
-use core::{cmp::Ordering, fmt::Debug};
+use core::convert::TryFrom;
+use core::fmt::Debug;
 use bytes::{BufMut, Bytes, BytesMut};
 use serde::{Serialize, de::DeserializeOwned};
-use serde_json::from_str;
+use serde_json::{Map, Value, from_str};
 use alloc::string::{String, ToString};
+use alloc::boxed::Box;
+use alloc::collections::btree_map::BTreeMap;
 use alloc::vec::Vec;
+use once_cell::race::OnceBox;
 use crate::alloc::borrow::ToOwned;
-use crate::types::{account::Account, amount::Amount, blob::Blob, definition::{Definitions, DefinitionField}, hash::Hash, path_set::PathSet, starray::STArray, stobject::STObject};
+use crate::errors::{Result, field_error, RippleBinaryCodecError, RippleBinaryCodecError::{BufferTooSmall, ConflictingFields, InvalidFieldValue, InvalidJson, MissingField, UnknownField}};
+use crate::validate::{COMMON_REQUIRED_FIELDS, REQUIRED_FIELDS};
+use crate::flags::named_flags_value;
+use crate::types::{account::{Account, EMPTY_ACCOUNT_FIELDS, write_vl_prefix}, amount::Amount, blob::Blob, definition::{Definitions, DefinitionField}, hash::Hash, issue::Issue, number::Number, path_set::PathSet, starray::sort_signers};
 
 /// A trait to be implemented by each field for serialization.
 pub trait SerializeField {
   fn to_bytes(&self) -> Option<Vec<u8>>;
 }
 
+/// The output of the serialization, which checks the remaining capacity of the buffer before each write, so that serializing
+/// into a fixed buffer which is too small fails instead of panicking.
+pub(crate) struct FieldWriter<'a, B: BufMut> {
+  out: &'a mut B,
+  /// Whether a write failed because the buffer is full.
+  pub(crate) overflowed: bool
+}
+
+impl<'a, B: BufMut> FieldWriter<'a, B> {
+  pub(crate) fn new(out: &'a mut B) -> Self {
+    FieldWriter { out, overflowed: false }
+  }
+
+  /// Write `src` into the buffer, nothing is written if it does not fit.
+  pub(crate) fn put_slice(&mut self, src: &[u8]) -> Option<()> {
+    if src.len() > self.out.remaining_mut() {
+      self.overflowed = true;
+      return None;
+    }
+    self.out.put_slice(src);
+    Some(())
+  }
+
+  pub(crate) fn put_u8(&mut self, n: u8) -> Option<()> {
+    self.put_slice(&[n])
+  }
+
+  pub(crate) fn put_u16(&mut self, n: u16) -> Option<()> {
+    self.put_slice(&n.to_be_bytes())
+  }
+
+  pub(crate) fn put_u32(&mut self, n: u32) -> Option<()> {
+    self.put_slice(&n.to_be_bytes())
+  }
+
+  pub(crate) fn put_u64(&mut self, n: u64) -> Option<()> {
+    self.put_slice(&n.to_be_bytes())
+  }
+}
+
+/// The former names of renamed fields and their current names in [`definitions.json`](https://github.com/KeystoneHQ/rippled_binary_codec/blob/main/src/fixtures/definitions.json),
+/// which are still accepted when serializing, so that a transaction JSON using a former name keeps working. The field codes
+/// are unchanged, and a deserialized transaction always uses the current names.
+pub const FIELD_ALIASES: [(&str, &str); 4] = [
+  ("TokenID", "NFTokenID"),
+  ("BuyOffer", "NFTokenBuyOffer"),
+  ("SellOffer", "NFTokenSellOffer"),
+  ("TokenTaxon", "NFTokenTaxon"),
+];
+
+/// Return the current name of a field given by its former name in [`FIELD_ALIASES`].
+fn current_name(field_name: &str) -> Option<&'static str> {
+  FIELD_ALIASES.iter().find(|(alias, _)| *alias == field_name).map(|(_, current)| *current)
+}
+
 /// A structure of ripple definitions.
 pub struct DefinitionFields{
-  pub definitions: Option<Definitions>
+  /// The parsed definitions, the lookup tables of the fields are built from them when the [`DefinitionFields`] is created,
+  /// so [`rebuild_lookups()`][`DefinitionFields::rebuild_lookups`] must be called after changing them.
+  pub definitions: Option<Definitions>,
+  sort_keys: BTreeMap<String, (i32, i32)>,
+  field_ids: BTreeMap<String, Bytes>,
+  field_names: BTreeMap<(i32, i32), String>
+}
+
+/// The top level keys of a transaction grouped by how they are serialized, see [`classify_fields()`][`DefinitionFields::classify_fields`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldReport {
+  /// The fields which are serialized, in canonical order.
+  pub serialized: Vec<String>,
+  /// The fields which are known but never serialized, like `hash`.
+  pub not_serialized: Vec<String>,
+  /// The keys which are not in the definitions, failing the serialization with
+  /// [`UnknownField`][`crate::errors::RippleBinaryCodecError::UnknownField`].
+  pub unknown: Vec<String>,
 }
 
 impl DefinitionFields {
@@ -27,9 +105,107 @@ impl DefinitionFields {
   ///
   pub fn new()-> Self{
     let definitions_json: &str = include_str!("fixtures/definitions.json");
-    Self {
-      definitions: from_str::<Definitions>(definitions_json).ok()
+    Self::with_definitions(from_str::<Definitions>(definitions_json).ok())
+  }
+
+  /// Init a DefinitionFields structure with a definitions JSON supplied at runtime, such as the result of the
+  /// [`server_definitions`](https://xrpl.org/docs/references/http-websocket-apis/public-api-methods/server-info-methods/server_definitions) method of rippled,
+  /// so that the fields of a newly activated amendment can be serialized without a new release of this crate.
+  ///
+  /// # Example
+  ///
+  ///```
+  ///use rippled_binary_codec::definition_fields::DefinitionFields;
+  ///
+  ///// `definitions_json` is fetched from rippled
+  ///fn from_str_example(definitions_json: &str){
+  ///  let fields = DefinitionFields::from_str(definitions_json).unwrap();
+  ///  assert!(fields.get_definition_field("Account".to_string()).is_some());
+  ///}
+  ///```
+  ///
+  /// # Errors
+  ///  If the JSON is invalid or any of `TYPES`, `LEDGER_ENTRY_TYPES`, `FIELDS`, `TRANSACTION_RESULTS` and `TRANSACTION_TYPES` is missing,
+  ///  `None` will be returned.
+  #[allow(clippy::should_implement_trait)]
+  pub fn from_str(definitions_json: &str) -> Option<Self> {
+    let definitions = from_str::<Definitions>(definitions_json).ok()?;
+    Some(Self::with_definitions(Some(definitions)))
+  }
+
+  /// Init a DefinitionFields structure with parsed [`Definitions`], building the lookup tables of the fields.
+  pub fn with_definitions(definitions: Option<Definitions>) -> Self {
+    let mut fields = Self {
+      definitions,
+      sort_keys: BTreeMap::new(),
+      field_ids: BTreeMap::new(),
+      field_names: BTreeMap::new()
+    };
+    fields.rebuild_lookups();
+    fields
+  }
+
+  /// Rebuild the lookup tables of the fields from [`definitions`][`DefinitionFields::definitions`], which must be called after
+  /// changing them, otherwise the fields are still serialized and decoded by the old definitions.
+  ///
+  /// # Example
+  ///
+  ///```
+  ///use rippled_binary_codec::definition_fields::DefinitionFields;
+  ///
+  ///fn rebuild_lookups_example(){
+  ///  let mut fields = DefinitionFields::new();
+  ///  if let Some(definitions) = fields.definitions.as_mut() {
+  ///    definitions.fields.remove("Memos");
+  ///  }
+  ///  fields.rebuild_lookups();
+  ///  assert_eq!(fields.get_field_id("Memos".to_string()), None);
+  ///}
+  ///```
+  pub fn rebuild_lookups(&mut self) {
+    // the sort keys and field ids are looked up for every serialized field, and the field names for every decoded field,
+    // so they are computed once here
+    let mut sort_keys = BTreeMap::new();
+    let mut field_ids = BTreeMap::new();
+    let mut field_names = BTreeMap::new();
+    if let Some(definitions) = &self.definitions {
+      for (field_name, field) in &definitions.fields {
+        if let Some(type_code) = definitions.types.get(&field.type_name) {
+          sort_keys.insert(field_name.to_owned(), (*type_code, field.nth));
+          field_ids.insert(field_name.to_owned(), Self::cal_field_id(field.nth, *type_code));
+          field_names.entry((*type_code, field.nth)).or_insert_with(|| field_name.to_owned());
+        }
+      }
     }
+    for (alias, current) in FIELD_ALIASES.iter() {
+      if let (Some(sort_key), Some(field_id)) = (sort_keys.get(*current).copied(), field_ids.get(*current).cloned()) {
+        sort_keys.insert(alias.to_string(), sort_key);
+        field_ids.insert(alias.to_string(), field_id);
+      }
+    }
+    self.sort_keys = sort_keys;
+    self.field_ids = field_ids;
+    self.field_names = field_names;
+  }
+
+  /// Return a [`DefinitionFields`] shared by the whole program, which is initialized by [`new()`][`DefinitionFields::new`] on the first call.
+  ///
+  /// Parsing [`definitions.json`](https://github.com/KeystoneHQ/rippled_binary_codec/blob/main/src/fixtures/definitions.json) is
+  /// much slower than serializing a transaction, so the functions taking an `Option<&DefinitionFields>` use it when `None` is given.
+  ///
+  /// # Example
+  ///
+  ///```
+  ///use rippled_binary_codec::definition_fields::DefinitionFields;
+  ///
+  ///fn global_example(){
+  ///  let fields = DefinitionFields::global();
+  ///  assert!(fields.get_definition_field("Account".to_string()).is_some());
+  ///}
+  ///```
+  pub fn global() -> &'static DefinitionFields {
+    static GLOBAL: OnceBox<DefinitionFields> = OnceBox::new();
+    GLOBAL.get_or_init(|| Box::new(DefinitionFields::new()))
   }
 
   ///Return a tuple sort key for a given field name.
@@ -81,21 +257,7 @@ impl DefinitionFields {
   /// # Errors
   ///  If it fails to get the `type_order` or `field_order`, `(-1,-1)` will be returned.
   pub fn get_field_sort_key(&self, field_name: String)-> (i32, i32){
-    match &self.definitions {
-      Some(definitions)=>{
-        if let Some(field_type_name) = definitions.fields.get(&field_name).and_then(|f| Some(f.to_owned().type_name)){
-          if let Some(type_sort_key) = definitions.types.get(&field_type_name).to_owned(){
-            if let Some(field_sort_key) = definitions.fields.get(&field_name).and_then(|f| Some(f.to_owned().nth)){
-              return (type_sort_key.to_owned(), field_sort_key)
-            }
-          }
-        }
-        return (-1,-1);
-      },
-      _=> {
-        return (-1,-1);
-      }
-    }
+    self.sort_keys.get(&field_name).copied().unwrap_or((-1,-1))
   }
 
   /// Ordering the input fields by it's sort key.
@@ -113,24 +275,15 @@ impl DefinitionFields {
   ///}
   ///```
   pub fn ordering_fields(&self, fields: Vec<String>)-> Vec<String>{
-    let mut sort_key: Vec<(i32, i32)> = Vec::new();
-    let mut keys = fields.to_owned();
-    for key in &keys {
-      let field = self.get_field_sort_key(key.to_string());
-      sort_key.push(field);
-    }
-    keys.sort_by(|a, b| {
-      let a_sort_key = self.get_field_sort_key(a.to_string());
-      let b_sort_key = self.get_field_sort_key(b.to_string());
-      match a_sort_key.0.cmp(&b_sort_key.0) {
-        Ordering::Equal => a_sort_key.1.cmp(&b_sort_key.1),
-        other => other,
-      }
-    });
+    let mut keys = fields;
+    keys.sort_by_cached_key(|key| self.sort_keys.get(key).copied().unwrap_or((-1,-1)));
     return keys
   }
   /// Get the value of field in data.
   ///
+  /// The whole `data` is converted to get one field, so the serializer borrows the fields from the parsed `serde_json::Map`
+  /// instead of calling it for each field.
+  ///
   /// # Example
   ///
   ///```
@@ -186,10 +339,187 @@ impl DefinitionFields {
   ///  If the `field_name` is not in [`definitions.json`] or `key` is not in the [`DefinitionField`][`crate::types::definition::DefinitionField`], `None` will be returned.
   pub fn get_definition_field(&self, field_name: String) -> Option<&DefinitionField>
   {
-    self.definitions.as_ref()?.fields.get(&field_name)
+    self.definition_field(&field_name)
+  }
+
+  /// Return whether a field is in [`definitions.json`](https://github.com/KeystoneHQ/rippled_binary_codec/blob/main/src/fixtures/definitions.json) or is a former name in [`FIELD_ALIASES`],
+  /// without allocating a `String` like [`get_definition_field()`][`DefinitionFields::get_definition_field`].
+  ///
+  /// # Example
+  ///
+  ///```
+  ///use rippled_binary_codec::definition_fields::DefinitionFields;
+  ///
+  ///fn field_exists_example(){
+  ///  let fields = DefinitionFields::new();
+  ///  assert!(fields.field_exists("Account"));
+  ///  assert!(!fields.field_exists("Comment"));
+  ///}
+  ///```
+  pub fn field_exists(&self, field_name: &str) -> bool {
+    self.definition_field(field_name).is_some()
   }
 
-  fn cal_field_id(&self, field_code: i32, type_code: i32) -> Bytes {
+  /// Check that a transaction JSON has the fields required by its transaction type, see [`REQUIRED_FIELDS`] and
+  /// [`COMMON_REQUIRED_FIELDS`], so that a transaction rippled would reject as malformed is caught before it's signed.
+  /// The transaction types not in [`REQUIRED_FIELDS`] are only checked for the common fields.
+  ///
+  /// # Example
+  ///
+  ///```
+  ///use rippled_binary_codec::definition_fields::DefinitionFields;
+  ///use serde_json::json;
+  ///
+  ///fn validate_required_example(){
+  ///  let tx = json!({"TransactionType": "Payment", "Account": "rwEJf6YSKALUaxRhvJ1S81PPmXzWhDW8on", "Fee": "12", "Sequence": 1, "Amount": "1000"});
+  ///  let error = DefinitionFields::new().validate_required(&tx).unwrap_err();
+  ///  println!("{}", error); // missing field Destination of Payment
+  ///}
+  ///```
+  ///
+  /// # Errors
+  ///  - [`InvalidJson`][`crate::errors::RippleBinaryCodecError::InvalidJson`] if `tx` is not a JSON object.
+  ///  - [`MissingField`][`crate::errors::RippleBinaryCodecError::MissingField`] of the first missing field, the `tx_type`
+  ///    of which is empty if the `TransactionType` itself is missing.
+  pub fn validate_required(&self, tx: &Value) -> Result<()> {
+    let tx = tx.as_object().ok_or_else(|| InvalidJson("transaction is not a JSON object".to_string()))?;
+    let tx_type = tx.get("TransactionType").and_then(Value::as_str).unwrap_or_default();
+    let required_fields = REQUIRED_FIELDS.iter().find(|(name, _)| *name == tx_type).map_or(&[][..], |(_, fields)| *fields);
+    let has_field = |field: &str| {
+      tx.contains_key(field) || FIELD_ALIASES.iter().any(|(alias, current)| *current == field && tx.contains_key(*alias))
+    };
+    match COMMON_REQUIRED_FIELDS.iter().chain(required_fields).find(|field| !has_field(**field)) {
+      Some(field) => Err(MissingField { tx_type: tx_type.to_string(), field: field.to_string() }),
+      None => Ok(()),
+    }
+  }
+
+  /// Group the top level keys of a transaction JSON into the serialized, the not serialized and the unknown fields, to tell
+  /// why a field is missing from the serialized transaction without stepping through [`serialize_tx`][`crate::serialize::serialize_tx`].
+  /// The signing fields are not told apart, see [`DefinitionField`]. If `tx` is not a JSON object, an empty report is returned.
+  ///
+  /// # Example
+  ///
+  ///```
+  ///use rippled_binary_codec::definition_fields::DefinitionFields;
+  ///use serde_json::json;
+  ///
+  ///fn classify_fields_example(){
+  ///  let tx = json!({"TransactionType": "Payment", "Fee": "12", "hash": "73734B61", "Comment": "rent"});
+  ///  let report = DefinitionFields::new().classify_fields(&tx);
+  ///  assert_eq!(report.serialized, ["TransactionType", "Fee"]);
+  ///  assert_eq!(report.not_serialized, ["hash"]);
+  ///  assert_eq!(report.unknown, ["Comment"]);
+  ///}
+  ///```
+  pub fn classify_fields(&self, tx: &Value) -> FieldReport {
+    let mut report = FieldReport::default();
+    let keys = match tx.as_object() {
+      Some(tx) => tx.keys().cloned().collect(),
+      None => return report,
+    };
+    for field_name in self.ordering_fields(keys) {
+      match self.get_definition_field(field_name.clone()) {
+        Some(field) if field.is_serialized => report.serialized.push(field_name),
+        Some(_) => report.not_serialized.push(field_name),
+        None => report.unknown.push(field_name),
+      }
+    }
+    report
+  }
+
+  /// Return whether a field is VL-encoded, that is, its serialized content is prefixed by a length.
+  ///
+  /// # Example
+  ///
+  ///```
+  ///use rippled_binary_codec::definition_fields::DefinitionFields;
+  ///
+  ///fn is_vl_encoded_example(){
+  ///  let fields = DefinitionFields::new();
+  ///  println!("{:?}", fields.is_vl_encoded("SigningPubKey".to_string())); // Some(true)
+  ///  println!("{:?}", fields.is_vl_encoded("Sequence".to_string())); // Some(false)
+  ///}
+  ///```
+  ///
+  /// # Errors
+  ///  If the `field_name` is not in [`definitions.json`](https://github.com/KeystoneHQ/rippled_binary_codec/blob/main/src/fixtures/definitions.json), `None` will be returned.
+  pub fn is_vl_encoded(&self, field_name: String) -> Option<bool> {
+    Some(self.get_definition_field(field_name)?.is_vl_encoded)
+  }
+
+  /// Return the code of a transaction type, e.g. `0` for `"Payment"`.
+  ///
+  /// # Example
+  ///
+  ///```
+  ///use rippled_binary_codec::definition_fields::DefinitionFields;
+  ///
+  ///fn transaction_type_code_example(){
+  ///  let fields = DefinitionFields::new();
+  ///  println!("{:?}", fields.transaction_type_code("Payment")); // Some(0)
+  ///  println!("{:?}", fields.transaction_type_name(0)); // Some("Payment")
+  ///}
+  ///```
+  ///
+  /// # Errors
+  ///  If the transaction type is unknown, `None` will be returned.
+  pub fn transaction_type_code(&self, name: &str) -> Option<i32> {
+    self.definitions.as_ref()?.transaction_types.get(name).copied()
+  }
+
+  /// Return the name of a transaction type code, the inverse of [`transaction_type_code()`][`DefinitionFields::transaction_type_code`].
+  pub fn transaction_type_name(&self, code: i32) -> Option<&str> {
+    let definitions = self.definitions.as_ref()?;
+    definitions.transaction_types.iter().find(|(_, type_code)| **type_code == code).map(|(name, _)| name.as_str())
+  }
+
+  /// Return the code of a ledger entry type, e.g. `114` for `"RippleState"`, to serialize a ledger entry instead of a transaction.
+  ///
+  /// # Example
+  ///
+  ///```
+  ///use rippled_binary_codec::definition_fields::DefinitionFields;
+  ///
+  ///fn ledger_entry_type_code_example(){
+  ///  let fields = DefinitionFields::new();
+  ///  println!("{:?}", fields.ledger_entry_type_code("RippleState")); // Some(114)
+  ///}
+  ///```
+  ///
+  /// # Errors
+  ///  If the ledger entry type is unknown, `None` will be returned.
+  pub fn ledger_entry_type_code(&self, name: &str) -> Option<i32> {
+    self.definitions.as_ref()?.ledger_entry_types.get(name).copied()
+  }
+
+  /// Return the code of a transaction result, e.g. `0` for `"tesSUCCESS"`.
+  ///
+  /// # Example
+  ///
+  ///```
+  ///use rippled_binary_codec::definition_fields::DefinitionFields;
+  ///
+  ///fn transaction_result_name_example(){
+  ///  let fields = DefinitionFields::new();
+  ///  println!("{:?}", fields.transaction_result_name(0)); // Some("tesSUCCESS")
+  ///  println!("{:?}", fields.transaction_result_code("tecUNFUNDED_PAYMENT")); // Some(104)
+  ///}
+  ///```
+  ///
+  /// # Errors
+  ///  If the transaction result is unknown, `None` will be returned.
+  pub fn transaction_result_code(&self, name: &str) -> Option<i32> {
+    self.definitions.as_ref()?.transaction_results.get(name).copied()
+  }
+
+  /// Return the name of a transaction result code, the inverse of [`transaction_result_code()`][`DefinitionFields::transaction_result_code`].
+  pub fn transaction_result_name(&self, code: i32) -> Option<&str> {
+    let definitions = self.definitions.as_ref()?;
+    definitions.transaction_results.iter().find(|(_, result_code)| **result_code == code).map(|(name, _)| name.as_str())
+  }
+
+  fn cal_field_id(field_code: i32, type_code: i32) -> Bytes {
     let mut buf = BytesMut::with_capacity(3);
     if type_code < 16 && field_code < 16 {
       let combined_code = (type_code << 4) | field_code;
@@ -209,13 +539,88 @@ impl DefinitionFields {
     return buf.freeze();
   }
 
+  /// Return the width in bytes of a fixed-width type, without any length prefix.
+  ///
+  /// `None` will be returned for variable-length types, such as `Blob`, `Amount`, `STObject`, `STArray` and `PathSet`.
+  ///
+  /// # Example
+  ///
+  ///```
+  ///use rippled_binary_codec::definition_fields::DefinitionFields;
+  ///
+  ///fn fixed_width_of_example(){
+  ///  let width = DefinitionFields::fixed_width_of("UInt32");
+  ///  println!("width: {:?}", width); // Some(4)
+  ///}
+  ///```
+  pub fn fixed_width_of(type_name: &str) -> Option<usize> {
+    match type_name {
+      "UInt8" => Some(1),
+      "UInt16" => Some(2),
+      "UInt32" => Some(4),
+      "UInt64" => Some(8),
+      "UInt96" => Some(12),
+      "Hash128" | "UInt128" => Some(16),
+      "Hash160" | "UInt160" | "AccountID" => Some(20),
+      "Hash192" | "UInt192" => Some(24),
+      "Hash256" => Some(32),
+      _ => None,
+    }
+  }
+
+  /// Read a field id prefix from the start of `bytes`, return `(type_code, field_code, consumed)`.
+  ///
+  /// `None` will be returned if `bytes` is too short to contain the field id.
+  pub(crate) fn read_field_id(bytes: &[u8]) -> Option<(i32, i32, usize)> {
+    let byte1 = *bytes.first()?;
+    let type_code = i32::from(byte1 >> 4);
+    let field_code = i32::from(byte1 & 0x0f);
+    match (type_code, field_code) {
+      (0, 0) => Some((i32::from(*bytes.get(1)?), i32::from(*bytes.get(2)?), 3)),
+      (0, _) => Some((i32::from(*bytes.get(1)?), field_code, 2)),
+      (_, 0) => Some((type_code, i32::from(*bytes.get(1)?), 2)),
+      _ => Some((type_code, field_code, 1)),
+    }
+  }
+
+  /// Find the field name and its definition by type code and field code.
+  pub(crate) fn find_field(&self, type_code: i32, field_code: i32) -> Option<(&String, &DefinitionField)> {
+    let field_name = self.field_names.get(&(type_code, field_code))?;
+    let field = self.definitions.as_ref()?.fields.get(field_name)?;
+    Some((field_name, field))
+  }
+
   /// Return the unique field id for a given field name, this field id consists of the type code ant field code, in 1 to 3 bytes
   /// depending on whether those values are "common"(<16) or "uncommon"<>=16>.
   pub fn get_field_id(&self, field_name: String) -> Option<Bytes>{
-    let field_type = &self.get_definition_field(field_name.clone())?.type_name;
-    let field_code =  self.get_definition_field(field_name)?.nth;
-    let type_code = self.definitions.as_ref()?.types.get(field_type)?.clone();
-    return Some(self.cal_field_id(field_code, type_code));
+    self.field_ids.get(&field_name).cloned()
+  }
+
+  /// Read the field id prefix at the start of `bytes`, the inverse of [`get_field_id()`][`DefinitionFields::get_field_id`].
+  /// Return the field name and the number of bytes the prefix takes.
+  ///
+  /// # Example
+  ///
+  ///```
+  ///use rippled_binary_codec::definition_fields::DefinitionFields;
+  ///
+  ///fn parse_field_id_example(){
+  ///  let fields = DefinitionFields::new();
+  ///  let (field_name, consumed) = fields.parse_field_id(b"\x20\x19").unwrap();
+  ///  assert_eq!(field_name, "OfferSequence");
+  ///  assert_eq!(consumed, 2);
+  ///}
+  ///```
+  ///
+  /// # Errors
+  ///  If `bytes` is too short, the field id is not encoded in its shortest form, or no field has the id, `None` will be returned.
+  pub fn parse_field_id(&self, bytes: &[u8]) -> Option<(String, usize)> {
+    let (type_code, field_code, consumed) = Self::read_field_id(bytes)?;
+    if Self::cal_field_id(field_code, type_code) != bytes[..consumed] {
+      return None;
+    }
+    let (field_name, _) = self.find_field(type_code, field_code)?;
+    Some((field_name.clone(), consumed))
   }
 
   /// Return a bytes object containing the serialized version of a field,
@@ -226,11 +631,15 @@ impl DefinitionFields {
   ///  - [`Account`][`crate::types::account::Account`] for serializing **AccountID** type of field.
   ///  - [`Amount`][`crate::types::amount::Amount`] for serializing **Amount** type of field.
   ///  - [`Blob`][`crate::types::blob::Blob`] for serializing **Blob** type of field.
-  ///  - [`Hash`][`crate::types::hash::Hash`] for serializing **Hash128**,**Hash160**,**Hash256** type of field.
+  ///  - [`Hash`][`crate::types::hash::Hash`] for serializing **Hash128**,**Hash160**,**Hash192**,**Hash256** type of field,
+  ///    and the hex strings of the fixed width **UInt96**, **UInt128**, **UInt160**, **UInt192** type of field.
+  ///  - [`Issue`][`crate::types::issue::Issue`] for serializing **Issue** type of field.
+  ///  - [`Number`][`crate::types::number::Number`] for serializing **Number** type of field.
   ///  - [`PathSet`][`crate::types::path_set::PathSet`] for serializing **PathSet** type of field.
-  ///  - [`STArray`][`crate::types::starray::STArray`] for serializing **STArray** type of field.
+  ///  - [`STArray`][`crate::types::starray::STArray`] for serializing **STArray** type of field, the entries of `Signers` are sorted by [`sort_signers`][`crate::types::starray::sort_signers`] first.
   ///  - [`STObject`][`crate::types::stobject::STObject`] for serializing **STObject** type of field.
-  ///  - [`to_be_bytes()`] for serializing **UInt8**, **UInt16**, **UInt32** type of field and slice to specific length.
+  ///  - [`to_be_bytes()`] for serializing **UInt8**, **UInt16**, **UInt32**, **UInt64** type of field and slice to specific length,
+  ///    **UInt64** is given either as a number or a hex string.
   ///
   /// [`to_be_bytes()`]: https://doc.rust-lang.org/std/primitive.u64.html#method.to_be_bytes
   ///
@@ -250,81 +659,259 @@ impl DefinitionFields {
   /// # Errors
   ///  If the field is failed to serialize, `None` will be returned.
   pub fn field_to_bytes(&self, field_name: String, field_val: serde_json::Value) -> Option<Vec<u8>> {
-    let field_type = self.get_definition_field(field_name.clone())?.type_name.clone();
-    let id_prefix: Bytes = self.get_field_id(field_name.clone())?;
-    let mut buf = BytesMut::with_capacity(0);
-    if field_name == "TransactionType".to_string() {
-      buf.extend_from_slice(&id_prefix);
-      let type_unit: Result<u16, _> = self.definitions.as_ref()?.transaction_types.get(field_val.as_str()?)?.clone().try_into();
-      match type_unit {
-        Ok(type_unit) => {
-          buf.put_u16(type_unit);
-          return Some(buf.to_vec());
-        },
-        Err(_) => {
-          return None;
-        }
+    let mut buf = Vec::new();
+    self.write_field(&field_name, &field_val, &mut FieldWriter::new(&mut buf))?;
+    Some(buf)
+  }
+
+  /// Serialize the fields of a transaction JSON in canonical order, writing them directly into `out` instead of returning
+  /// a new buffer, like [`serialize_tx`][`crate::serialize::serialize_tx`] without the hex encoding. Each field is written
+  /// straight from the JSON into `out`, so a `no_std` target with little heap can serialize into a fixed buffer.
+  ///
+  /// # Example
+  ///
+  ///```
+  ///use serde_json::json;
+  ///use rippled_binary_codec::definition_fields::DefinitionFields;
+  ///
+  ///fn serialize_into_example(){
+  ///  let fields = DefinitionFields::new();
+  ///  let tx = json!({"TransactionType": "AccountSet", "Sequence": 5, "Fee": "12"});
+  ///  let mut out = Vec::new();
+  ///  fields.serialize_into(&tx, false, &mut out).unwrap();
+  ///  assert_eq!(hex::encode_upper(out), "120003240000000568400000000000000C");
+  ///}
+  ///```
+  ///
+  /// # Errors
+  ///  - [`InvalidJson`][`crate::errors::RippleBinaryCodecError::InvalidJson`] if `tx` is not a JSON object.
+  ///  - [`BufferTooSmall`][`crate::errors::RippleBinaryCodecError::BufferTooSmall`] if `out` is full before all the fields are written.
+  ///  - The error of the first field failed to serialize, see [`serialize_tx_collect_errors`][`crate::serialize::serialize_tx_collect_errors`].
+  ///
+  ///  The fields before the failed one, and possibly a part of it, have been written into `out` when an error is returned.
+  pub fn serialize_into<B: BufMut>(&self, tx: &Value, for_signing: bool, out: &mut B) -> Result<()> {
+    let tx = tx.as_object().ok_or_else(|| InvalidJson("transaction is not a JSON object".to_string()))?;
+    self.write_fields(tx, None, for_signing, &mut FieldWriter::new(out), None)
+  }
+
+  /// Write the top level fields of a transaction in canonical order, or in `field_order` if it's given. The error of the
+  /// first failed field is returned, or if `errors` is given, the errors of all the failed fields are collected into it
+  /// and the other fields are still written.
+  ///
+  /// This is the loop shared by all the serialize functions, so `Flags` given as a list of flag names is resolved here,
+  /// see [`resolve_flags`][`crate::flags::resolve_flags`].
+  pub(crate) fn write_fields<B: BufMut>(
+    &self,
+    tx: &Map<String, Value>,
+    field_order: Option<&[String]>,
+    for_signing: bool,
+    out: &mut FieldWriter<B>,
+    mut errors: Option<&mut Vec<RippleBinaryCodecError>>,
+  ) -> Result<()> {
+    // a field given by both its former and its current name would be written twice
+    for (alias, current) in FIELD_ALIASES.iter() {
+      if tx.contains_key(*alias) && tx.contains_key(*current) {
+        report_error(ConflictingFields(alias.to_string(), current.to_string()), &mut errors)?;
       }
     }
-    let slice: Vec<u8> = match field_type.as_str() {
+    let mut flags = None;
+    let mut skip_flags = false;
+    match named_flags_value(tx) {
+      Ok(value) => flags = value.map(Value::from),
+      Err(error) => {
+        // reported once, instead of again when the list of flag names fails to serialize
+        skip_flags = true;
+        report_error(error, &mut errors)?;
+      },
+    }
+    let field_names: Vec<&str> = match field_order {
+      Some(field_order) => field_order.iter().map(String::as_str).collect(),
+      None => self.ordered_keys(tx),
+    };
+    for field_name in field_names {
+      if field_name == "Flags" && skip_flags {
+        continue
+      }
+      let field_val = match (field_name, &flags) {
+        ("Flags", Some(flags)) => Some(flags),
+        _ => tx.get(field_name),
+      };
+      if let Err(error) = self.write_top_field(field_name, field_val, for_signing, out) {
+        report_error(error, &mut errors)?;
+      }
+    }
+    Ok(())
+  }
+
+  fn write_top_field<B: BufMut>(&self, field_name: &str, field_val: Option<&Value>, for_signing: bool, out: &mut FieldWriter<B>) -> Result<()> {
+    let definition_field = self.definition_field(field_name).ok_or_else(|| UnknownField(field_name.to_string()))?;
+    if !definition_field.is_serialized || (for_signing && !definition_field.is_signing_field) {
+      return Ok(());
+    }
+    let field_val = field_val.ok_or_else(|| InvalidFieldValue(field_name.to_string()))?;
+    if self.write_field(field_name, field_val, out).is_none() {
+      if out.overflowed {
+        return Err(BufferTooSmall);
+      }
+      return Err(field_error(field_name.to_string(), field_val, &definition_field.type_name));
+    }
+    Ok(())
+  }
+
+  /// The keys of a JSON object in canonical order, the keys not in the definitions come first.
+  fn ordered_keys<'v>(&self, object: &'v Map<String, Value>) -> Vec<&'v str> {
+    let mut keys: Vec<&str> = object.keys().map(String::as_str).collect();
+    keys.sort_by_key(|key| self.sort_keys.get(*key).copied().unwrap_or((-1,-1)));
+    keys
+  }
+
+  fn definition_field(&self, field_name: &str) -> Option<&DefinitionField> {
+    let fields = &self.definitions.as_ref()?.fields;
+    fields.get(field_name).or_else(|| fields.get(current_name(field_name)?))
+  }
+
+  /// Write the field id prefix and the content of a field into `out`.
+  pub(crate) fn write_field<B: BufMut>(&self, field_name: &str, field_val: &Value, out: &mut FieldWriter<B>) -> Option<()> {
+    let field_type = self.definition_field(field_name)?.type_name.as_str();
+    let id_prefix = self.field_ids.get(field_name)?;
+    // a `LedgerEntryType` is serialized like a `TransactionType` from its name, or from its code as a plain `UInt16`
+    if field_name == "TransactionType" || (field_name == "LedgerEntryType" && field_val.is_string()) {
+      let definitions = self.definitions.as_ref()?;
+      let type_codes = if field_name == "TransactionType" { &definitions.transaction_types } else { &definitions.ledger_entry_types };
+      let type_code = u16::try_from(*type_codes.get(field_val.as_str()?)?).ok()?;
+      out.put_slice(id_prefix)?;
+      return out.put_u16(type_code);
+    }
+    match field_type {
+      "UInt8" => {
+        let input = u8::try_from(field_val.as_u64()?).ok()?;
+        out.put_slice(id_prefix)?;
+        out.put_u8(input)
+      },
+      "UInt16" => {
+        let input = u16::try_from(field_val.as_u64()?).ok()?;
+        out.put_slice(id_prefix)?;
+        out.put_u16(input)
+      },
+      "UInt32" => {
+        let input = u32::try_from(field_val.as_u64()?).ok()?;
+        out.put_slice(id_prefix)?;
+        out.put_u32(input)
+      },
+      "UInt64" => {
+        // rippled renders UInt64 as a hex string in JSON
+        let input: u64 = match field_val.as_str() {
+          Some(hex) if !hex.is_empty() && hex.len() <= 16 && hex.chars().all(|c| c.is_ascii_hexdigit()) => {
+            u64::from_str_radix(hex, 16).ok()?
+          },
+          Some(_) => return None,
+          None => field_val.as_u64()?,
+        };
+        out.put_slice(id_prefix)?;
+        out.put_u64(input)
+      },
+      _ => {
+        out.put_slice(id_prefix)?;
+        self.write_value(field_name, field_type, field_val, out)
+      },
+    }
+  }
+
+  /// Write the content of a field of the types which are not integers.
+  fn write_value<B: BufMut>(&self, field_name: &str, field_type: &str, field_val: &Value, out: &mut FieldWriter<B>) -> Option<()> {
+    match field_type {
       "AccountID" => {
-        Account{data: field_val}.to_bytes()
+        if field_val == "" && EMPTY_ACCOUNT_FIELDS.contains(&field_name) {
+          return out.put_u8(0);
+        }
+        Account::write(field_val, out)
       },
       "Amount" =>{
-        Amount{data: field_val}.to_bytes()
+        Amount::write(field_val, out)
       },
       "Blob" =>{
-        Blob{data: field_val}.to_bytes()
+        Blob::write(field_val, out)
+      },
+      "UInt96"=>{
+        Hash::write(field_val, 12, out)
+      },
+      "Hash128" | "UInt128"=>{
+        Hash::write(field_val, 16, out)
       },
-      "Hash128"=>{
-        Hash{
-          data: field_val,
-          len: 16
-        }.to_bytes()
+      "Hash160" | "UInt160"=>{
+        Hash::write(field_val, 20, out)
       },
-      "Hash160"=>{
-        Hash{
-          data: field_val,
-          len: 20
-        }.to_bytes()
+      "Hash192" | "UInt192"=>{
+        Hash::write(field_val, 24, out)
       },
       "Hash256"=>{
-        Hash{
-          data: field_val,
-          len: 32
-        }.to_bytes()
+        Hash::write(field_val, 32, out)
+      },
+      "Vector256"=>{
+        let hashes = field_val.as_array()?;
+        write_vl_prefix(hashes.len() * 32, out)?;
+        for hash in hashes {
+          Hash::write(hash, 32, out)?;
+        }
+        Some(())
+      },
+      "Issue"=>{
+        Issue::write(field_val, out)
+      },
+      "Number"=>{
+        Number::write(field_val, out)
       },
       "PathSet"=>{
-        PathSet {data: field_val}.to_bytes()
+        PathSet::write(field_val, out)
       },
       "STArray"=>{
-        STArray {data: field_val, definition_fields: &self}.to_bytes()
+        // rippled only accepts `Signers` sorted by account
+        if field_name == "Signers" {
+          return self.write_array(&sort_signers(field_val)?, out);
+        }
+        self.write_array(field_val, out)
       },
       "STObject"=>{
-        STObject{data: field_val, definition_fields: &self}.to_bytes()
+        self.write_object(field_val, out)
       },
-      "UInt8"=>{
-        let input: u64 = field_val.as_u64()?;
-        let len = input.to_be_bytes().len();
-        Some(input.to_be_bytes()[len-1..].to_vec())
-      },
-      "UInt16"=>{
-        let input: u64 = field_val.as_u64()?;
-        let len = input.to_be_bytes().len();
-        Some(input.to_be_bytes()[len-2..].to_vec())
-      },
-      "UInt32"=>{
-        let input: u64 = field_val.as_u64()?;
-        let len = input.to_be_bytes().len();
-        Some(input.to_be_bytes()[len-4..].to_vec())
-      }
       _ => {
         None
       }
-    }?;
-    buf.extend_from_slice(&id_prefix);
-    buf.extend_from_slice(&slice);
-    return Some(buf.to_vec());
+    }
+  }
+
+  /// Write the content of an `STArray` field, each element is an `STObject` wrapped by its field name, like
+  /// `{"Memo": {...}}`, followed by the `ArrayEndMarker`.
+  pub(crate) fn write_array<B: BufMut>(&self, field_val: &Value, out: &mut FieldWriter<B>) -> Option<()> {
+    for element in field_val.as_array()? {
+      if let Some((wrapper, _)) = element.as_object().and_then(|element| element.iter().next()) {
+        self.write_field(wrapper, element, out)?;
+      }
+    }
+    out.put_slice(self.field_ids.get("ArrayEndMarker")?)
+  }
+
+  /// Write the content of an `STObject` field, the serialized fields of the object wrapped by its field name, like
+  /// `{"SignerEntry": {...}}`, in canonical order followed by the `ObjectEndMarker`.
+  pub(crate) fn write_object<B: BufMut>(&self, field_val: &Value, out: &mut FieldWriter<B>) -> Option<()> {
+    let inner_obj = field_val.as_object()?.values().next()?.as_object()?;
+    for field_name in self.ordered_keys(inner_obj) {
+      if self.definition_field(field_name)?.is_serialized {
+        self.write_field(field_name, inner_obj.get(field_name)?, out)?;
+      }
+    }
+    out.put_slice(self.field_ids.get("ObjectEndMarker")?)
+  }
+}
+
+/// Collect `error` into `errors` if it's given, otherwise return it.
+fn report_error(error: RippleBinaryCodecError, errors: &mut Option<&mut Vec<RippleBinaryCodecError>>) -> Result<()> {
+  match errors {
+    Some(errors) => {
+      errors.push(error);
+      Ok(())
+    },
+    None => Err(error),
   }
 }
 
@@ -334,6 +921,7 @@ mod tests {
 
   use serde_json::{Value, json};
 
+  use crate::fixtures::ACCOUNT_DELETE;
   use crate::types::definition::DefinitionField;
 
   use super::*;
@@ -349,11 +937,43 @@ mod tests {
     assert_eq!(after_sort, expected);
   }
 
+  #[test]
+  fn test_from_str(){
+    let mut definitions: Value = from_str(include_str!("fixtures/definitions.json")).unwrap();
+    let new_field = json!(["NewAmendmentField", {"nth": 99, "isVLEncoded": false, "isSerialized": true, "isSigningField": true, "type": "UInt32"}]);
+    definitions["FIELDS"].as_array_mut().unwrap().push(new_field);
+    let fields = DefinitionFields::from_str(&definitions.to_string()).unwrap();
+    assert_eq!(fields.get_field_sort_key("NewAmendmentField".to_string()), (2, 99));
+    assert_eq!(fields.field_to_bytes("NewAmendmentField".to_string(), Value::from(1)).unwrap(), b"\x20\x63\x00\x00\x00\x01");
+
+    definitions.as_object_mut().unwrap().remove("TRANSACTION_TYPES");
+    assert!(DefinitionFields::from_str(&definitions.to_string()).is_none());
+    assert!(DefinitionFields::from_str("{").is_none());
+  }
+
   #[test]
   fn test_get_field_sort_key(){
     let fields = DefinitionFields::new();
     let account_sort_key = fields.get_field_sort_key("Account".to_string());
     assert_eq!(account_sort_key,(8,1));
+    assert_eq!(fields.get_field_sort_key("UnknownField".to_string()),(-1,-1));
+    assert_eq!(fields.get_field_id("UnknownField".to_string()), None);
+  }
+
+  #[test]
+  fn test_fixed_width_of(){
+    assert_eq!(DefinitionFields::fixed_width_of("UInt8"), Some(1));
+    assert_eq!(DefinitionFields::fixed_width_of("UInt16"), Some(2));
+    assert_eq!(DefinitionFields::fixed_width_of("UInt32"), Some(4));
+    assert_eq!(DefinitionFields::fixed_width_of("UInt64"), Some(8));
+    assert_eq!(DefinitionFields::fixed_width_of("Hash128"), Some(16));
+    assert_eq!(DefinitionFields::fixed_width_of("AccountID"), Some(20));
+    assert_eq!(DefinitionFields::fixed_width_of("Hash256"), Some(32));
+    assert_eq!(DefinitionFields::fixed_width_of("UInt96"), Some(12));
+    assert_eq!(DefinitionFields::fixed_width_of("UInt192"), Some(24));
+    assert_eq!(DefinitionFields::fixed_width_of("Blob"), None);
+    assert_eq!(DefinitionFields::fixed_width_of("Amount"), None);
+    assert_eq!(DefinitionFields::fixed_width_of("STObject"), None);
   }
 
   #[test]
@@ -376,8 +996,8 @@ mod tests {
   #[test]
   fn test_load_def() {
     let definitions = DefinitionFields::new().definitions.unwrap();
-    assert_eq!(definitions.types.len(),20);
-    assert_eq!(definitions.transaction_types.len(),31);
+    assert_eq!(definitions.types.len(),24);
+    assert_eq!(definitions.transaction_types.len(),37);
     assert_eq!(definitions.transaction_results.len(),127);
     let generic_field = DefinitionField {
       nth: 0,
@@ -401,6 +1021,221 @@ mod tests {
     assert_eq!(is_serialized, true);
     assert_eq!(is_signing_field, true);
   }
+  #[test]
+  fn test_field_exists(){
+    let fields = DefinitionFields::new();
+    assert!(fields.field_exists("Account"));
+    assert!(fields.field_exists("ObjectEndMarker"));
+    assert!(!fields.field_exists("Comment"));
+    assert!(!fields.field_exists("account"));
+    assert!(!DefinitionFields::with_definitions(None).field_exists("Account"));
+  }
+
+  #[test]
+  fn test_field_aliases(){
+    let fields = DefinitionFields::new();
+    let hash = "0B089EC2D5CBB6F514C5965853474D40D10C0E839A539480DC84D273E3584A4D";
+    for (alias, current) in FIELD_ALIASES.iter() {
+      assert!(fields.field_exists(alias), "{}", alias);
+      assert_eq!(fields.get_field_id(alias.to_string()), fields.get_field_id(current.to_string()));
+      assert_eq!(fields.get_field_sort_key(alias.to_string()), fields.get_field_sort_key(current.to_string()));
+    }
+    let mut expected = Vec::new();
+    fields.serialize_into(&json!({"TransactionType": "NFTokenAcceptOffer", "Fee": "10", "NFTokenSellOffer": hash}), true, &mut expected).unwrap();
+    let mut out = Vec::new();
+    fields.serialize_into(&json!({"TransactionType": "NFTokenAcceptOffer", "Fee": "10", "SellOffer": hash}), true, &mut out).unwrap();
+    assert_eq!(out, expected);
+    // the deserialized transaction uses the current name
+    assert_eq!(fields.parse_field_id(b"\x50\x1d"), Some(("NFTokenSellOffer".to_string(), 2)));
+
+    let mint = json!({"TransactionType": "NFTokenMint", "Account": "rwEJf6YSKALUaxRhvJ1S81PPmXzWhDW8on", "Fee": "10", "Sequence": 1, "TokenTaxon": 0});
+    assert_eq!(fields.validate_required(&mint), Ok(()));
+
+    let tx = json!({"TransactionType": "NFTokenBurn", "TokenID": hash, "NFTokenID": hash});
+    let mut out = Vec::new();
+    assert_eq!(fields.serialize_into(&tx, true, &mut out), Err(ConflictingFields("TokenID".to_string(), "NFTokenID".to_string())));
+  }
+
+  #[test]
+  fn test_rebuild_lookups(){
+    let mut fields = DefinitionFields::new();
+    let mut memo_data = fields.get_definition_field("MemoData".to_string()).unwrap().clone();
+    memo_data.nth = 99;
+    fields.definitions.as_mut().unwrap().fields.insert("Comment".to_string(), memo_data);
+    assert_eq!(fields.get_field_id("Comment".to_string()), None);
+    fields.rebuild_lookups();
+    assert_eq!(fields.get_field_id("Comment".to_string()), Some(Bytes::from_static(b"\x70\x63")));
+    assert_eq!(fields.get_field_sort_key("Comment".to_string()), (7, 99));
+    assert_eq!(fields.parse_field_id(b"\x70\x63"), Some(("Comment".to_string(), 2)));
+  }
+
+  #[test]
+  fn test_classify_fields(){
+    let fields = DefinitionFields::new();
+    let tx = json!({
+      "TransactionType": "OfferCreate",
+      "Account": "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys",
+      "Fee": "10",
+      "Flags": 524288,
+      "TxnSignature": "3044",
+      "hash": "73734B611DDA23D3F5F62E20A173B78AB8406AC5015094DA53F53D39B9EDB06C",
+      "LastLedgerSeq": 1,
+      "ledger_index": 1
+    });
+    let to_strings = |names: &[&str]| names.iter().map(|name| name.to_string()).collect::<Vec<String>>();
+    let expected = FieldReport {
+      serialized: to_strings(&["TransactionType", "Flags", "Fee", "TxnSignature", "Account"]),
+      not_serialized: to_strings(&["hash"]),
+      unknown: to_strings(&["LastLedgerSeq", "ledger_index"]),
+    };
+    assert_eq!(fields.classify_fields(&tx), expected);
+    assert_eq!(fields.classify_fields(&json!([1])), FieldReport::default());
+  }
+
+  #[test]
+  fn test_validate_required(){
+    let fields = DefinitionFields::new();
+    let missing = |tx_type: &str, field: &str| Err(MissingField { tx_type: tx_type.to_string(), field: field.to_string() });
+    let mut tx = json!({"TransactionType": "Payment", "Account": "rwEJf6YSKALUaxRhvJ1S81PPmXzWhDW8on", "Fee": "12", "Sequence": 1});
+    assert_eq!(fields.validate_required(&tx), missing("Payment", "Destination"));
+    tx["Destination"] = json!("rNp5zaiaR3maZ8zALz5CWnqRYXWkeGhteS");
+    assert_eq!(fields.validate_required(&tx), missing("Payment", "Amount"));
+    tx["Amount"] = json!("1000");
+    assert_eq!(fields.validate_required(&tx), Ok(()));
+
+    tx["TransactionType"] = json!("OfferCreate");
+    assert_eq!(fields.validate_required(&tx), missing("OfferCreate", "TakerGets"));
+    // only the common fields of a transaction type out of the table
+    tx["TransactionType"] = json!("AccountSet");
+    assert_eq!(fields.validate_required(&tx), Ok(()));
+    tx.as_object_mut().unwrap().remove("Fee");
+    assert_eq!(fields.validate_required(&tx), missing("AccountSet", "Fee"));
+    tx.as_object_mut().unwrap().remove("TransactionType");
+    assert_eq!(fields.validate_required(&tx), missing("", "TransactionType"));
+    assert!(fields.validate_required(&json!([])).is_err());
+
+    // every field in the table is defined
+    for (tx_type, required_fields) in REQUIRED_FIELDS {
+      assert!(fields.transaction_type_code(tx_type).is_some(), "{}", tx_type);
+      assert!(required_fields.iter().all(|field| fields.field_exists(field)), "{}", tx_type);
+    }
+  }
+
+  #[test]
+  fn test_validate_required_per_type(){
+    let fields = DefinitionFields::new();
+    let account = "rNp5zaiaR3maZ8zALz5CWnqRYXWkeGhteS";
+    let hash = "0B089EC2D5CBB6F514C5965853474D40D10C0E839A539480DC84D273E3584A4D";
+    let public_key = "02B87CEB1507849B6473773155827C0B8C15CB311C6876FBD7FAB95F06D3E18E39";
+    let usd = json!({"currency": "USD", "issuer": account, "value": "10"});
+    let xrp_issue = json!({"currency": "XRP"});
+    let usd_issue = json!({"currency": "USD", "issuer": account});
+    // the required fields of each entry of the table, in the same order
+    let examples = [
+      ("Payment", json!({"Destination": account, "Amount": "1000"})),
+      ("OfferCreate", json!({"TakerGets": "1000", "TakerPays": usd})),
+      ("OfferCancel", json!({"OfferSequence": 7})),
+      ("TrustSet", json!({"LimitAmount": usd})),
+      ("AccountDelete", json!({"Destination": account})),
+      ("EscrowCreate", json!({"Destination": account, "Amount": "1000"})),
+      ("EscrowFinish", json!({"Owner": account, "OfferSequence": 7})),
+      ("EscrowCancel", json!({"Owner": account, "OfferSequence": 7})),
+      ("PaymentChannelCreate", json!({"Amount": "1000", "Destination": account, "SettleDelay": 86400, "PublicKey": public_key})),
+      ("PaymentChannelFund", json!({"Channel": hash, "Amount": "1000"})),
+      ("PaymentChannelClaim", json!({"Channel": hash})),
+      ("SignerListSet", json!({"SignerQuorum": 0})),
+      ("CheckCreate", json!({"Destination": account, "SendMax": "1000"})),
+      ("CheckCash", json!({"CheckID": hash})),
+      ("CheckCancel", json!({"CheckID": hash})),
+      ("TicketCreate", json!({"TicketCount": 1})),
+      ("NFTokenMint", json!({"NFTokenTaxon": 0})),
+      ("NFTokenBurn", json!({"NFTokenID": hash})),
+      ("NFTokenCreateOffer", json!({"NFTokenID": hash, "Amount": "1000"})),
+      ("AMMCreate", json!({"Amount": "1000", "Amount2": usd, "TradingFee": 500})),
+      ("AMMDeposit", json!({"Asset": xrp_issue, "Asset2": usd_issue})),
+      ("AMMWithdraw", json!({"Asset": xrp_issue, "Asset2": usd_issue})),
+      ("AMMVote", json!({"Asset": xrp_issue, "Asset2": usd_issue, "TradingFee": 500})),
+      ("AMMBid", json!({"Asset": xrp_issue, "Asset2": usd_issue})),
+      ("AMMDelete", json!({"Asset": xrp_issue, "Asset2": usd_issue})),
+    ];
+    assert_eq!(examples.len(), REQUIRED_FIELDS.len());
+    for ((tx_type, required_fields), (example_type, example)) in REQUIRED_FIELDS.iter().zip(examples.iter()) {
+      assert_eq!(tx_type, example_type);
+      let mut tx = json!({"TransactionType": tx_type, "Account": "rwEJf6YSKALUaxRhvJ1S81PPmXzWhDW8on", "Fee": "12", "Sequence": 1});
+      tx.as_object_mut().unwrap().extend(example.as_object().unwrap().clone());
+      assert_eq!(fields.validate_required(&tx), Ok(()), "{}", tx_type);
+      assert!(crate::serialize::serialize_tx_value(&tx, false, Some(&fields)).is_some(), "{}", tx_type);
+      for field in required_fields.iter() {
+        let mut missing = tx.clone();
+        missing.as_object_mut().unwrap().remove(*field);
+        let error = MissingField { tx_type: tx_type.to_string(), field: field.to_string() };
+        assert_eq!(fields.validate_required(&missing), Err(error));
+      }
+    }
+  }
+
+  #[test]
+  fn test_fixed_width_uint_to_bytes(){
+    // fields of the wide fixed width types, like the ones of a newer amendment
+    let mut definitions: Value = from_str(include_str!("fixtures/definitions.json")).unwrap();
+    let fields = definitions["FIELDS"].as_array_mut().unwrap();
+    for (name, nth, type_name) in [("TestUInt96", 1, "UInt96"), ("TestHash192", 1, "Hash192")] {
+      fields.push(json!([name, {"nth": nth, "isVLEncoded": false, "isSerialized": true, "isSigningField": true, "type": type_name}]));
+    }
+    let fields = DefinitionFields::from_str(&definitions.to_string()).unwrap();
+    let output = fields.field_to_bytes("TestUInt96".to_string(), json!("0102030405060708090A0B0C")).unwrap();
+    assert_eq!(hex::encode_upper(output), "01140102030405060708090A0B0C");
+    let issuance_id = "00000004A407AF5856CCF3C42619DAA925813FC955C72983";
+    let output = fields.field_to_bytes("TestHash192".to_string(), json!(issuance_id)).unwrap();
+    assert_eq!(hex::encode_upper(&output), format!("0115{}", issuance_id));
+    assert_eq!(fields.field_to_bytes("TestUInt96".to_string(), json!("0102")), None);
+    assert_eq!(fields.field_to_bytes("TestHash192".to_string(), json!("0102030405060708090A0B0C")), None);
+    let tx = crate::serialize::deserialize_tx(hex::encode_upper(&output), Some(&fields)).unwrap();
+    assert_eq!(tx, json!({"TestHash192": issuance_id}));
+  }
+
+  #[test]
+  fn test_is_vl_encoded(){
+    let fields = DefinitionFields::new();
+    for field_name in ["Account", "SigningPubKey", "TxnSignature", "MemoData"] {
+      assert_eq!(fields.is_vl_encoded(field_name.to_string()), Some(true));
+    }
+    for field_name in ["Sequence", "Flags"] {
+      assert_eq!(fields.is_vl_encoded(field_name.to_string()), Some(false));
+    }
+    assert_eq!(fields.is_vl_encoded("UnknownField".to_string()), None);
+    // the content of a VL-encoded field follows a length prefix
+    assert_eq!(fields.field_to_bytes("SigningPubKey".to_string(), Value::from("")).unwrap(), b"\x73\x00");
+    assert_eq!(fields.field_to_bytes("MemoData".to_string(), Value::from("72656E74")).unwrap(), b"\x7d\x04rent");
+    assert_eq!(fields.field_to_bytes("Sequence".to_string(), Value::from(5)).unwrap(), b"\x24\x00\x00\x00\x05");
+  }
+
+  #[test]
+  fn test_transaction_types_and_results(){
+    let fields = DefinitionFields::new();
+    assert_eq!(fields.transaction_type_code("Payment"), Some(0));
+    assert_eq!(fields.transaction_type_code("AccountDelete"), Some(21));
+    assert_eq!(fields.transaction_type_name(21), Some("AccountDelete"));
+    assert_eq!(fields.transaction_type_code("Transfer"), None);
+    assert_eq!(fields.transaction_type_name(1000), None);
+
+    assert_eq!(fields.transaction_result_name(0), Some("tesSUCCESS"));
+    assert_eq!(fields.transaction_result_code("tecUNFUNDED_PAYMENT"), Some(104));
+    assert_eq!(fields.transaction_result_name(104), Some("tecUNFUNDED_PAYMENT"));
+    assert_eq!(fields.transaction_result_code("tesFAILURE"), None);
+  }
+
+  #[test]
+  fn test_ledger_entry_types(){
+    let fields = DefinitionFields::new();
+    assert_eq!(fields.ledger_entry_type_code("RippleState"), Some(114));
+    assert_eq!(fields.ledger_entry_type_code("AccountRoot"), Some(97));
+    assert_eq!(fields.ledger_entry_type_code("Payment"), None);
+    assert_eq!(fields.field_to_bytes("LedgerEntryType".to_string(), Value::from("RippleState")).unwrap(), b"\x11\x00\x72");
+    assert_eq!(fields.field_to_bytes("LedgerEntryType".to_string(), Value::from(114)).unwrap(), b"\x11\x00\x72");
+    assert_eq!(fields.field_to_bytes("LedgerEntryType".to_string(), Value::from("Payment")), None);
+  }
+
   #[test]
   fn test_get_field_id() {
     let fields = DefinitionFields::new();
@@ -422,4 +1257,103 @@ mod tests {
     assert_eq!(result.get("TxnSignature").unwrap().slice(..),  b"\x74"[..]);
     assert_eq!(result.get("Account").unwrap().slice(..),  b"\x81"[..]);
   }
+
+  #[test]
+  fn test_field_to_bytes_uint64() {
+    let fields = DefinitionFields::new();
+    let expected = b"\x34\x00\x00\x00\x00\x00\x00\x00\x6c";
+    assert_eq!(fields.field_to_bytes("OwnerNode".to_string(), Value::from("000000000000006C")).unwrap(), expected);
+    assert_eq!(fields.field_to_bytes("OwnerNode".to_string(), Value::from("6c")).unwrap(), expected);
+    assert_eq!(fields.field_to_bytes("OwnerNode".to_string(), Value::from(108)).unwrap(), expected);
+    assert_eq!(fields.field_to_bytes("OwnerNode".to_string(), Value::from(u64::MAX)).unwrap(), b"\x34\xff\xff\xff\xff\xff\xff\xff\xff");
+
+    assert_eq!(fields.field_to_bytes("OwnerNode".to_string(), Value::from("1000000000000006C")), None);
+    assert_eq!(fields.field_to_bytes("OwnerNode".to_string(), Value::from("+6C")), None);
+    assert_eq!(fields.field_to_bytes("OwnerNode".to_string(), Value::from("")), None);
+    assert_eq!(fields.field_to_bytes("OwnerNode".to_string(), Value::from(-1)), None);
+    assert_eq!(fields.field_to_bytes("OwnerNode".to_string(), Value::from(1.5)), None);
+  }
+
+  #[test]
+  fn test_field_to_bytes_uint8() {
+    let fields = DefinitionFields::new();
+    // `TickSize` has type code 16 and field code 16
+    assert_eq!(fields.field_to_bytes("TickSize".to_string(), Value::from(0)).unwrap(), b"\x00\x10\x10\x00");
+    assert_eq!(fields.field_to_bytes("TickSize".to_string(), Value::from(127)).unwrap(), b"\x00\x10\x10\x7f");
+    assert_eq!(fields.field_to_bytes("TickSize".to_string(), Value::from(255)).unwrap(), b"\x00\x10\x10\xff");
+    // out of range values are rejected instead of truncated
+    assert_eq!(fields.field_to_bytes("TickSize".to_string(), Value::from(256)), None);
+    assert_eq!(fields.field_to_bytes("TickSize".to_string(), Value::from(-1)), None);
+  }
+
+  #[test]
+  fn test_parse_field_id() {
+    let fields = DefinitionFields::new();
+    let expected = [
+      ("TransactionType", &b"\x12"[..]),
+      ("Flags", b"\x22"),
+      ("Sequence", b"\x24"),
+      ("Expiration", b"\x2a"),
+      ("OfferSequence", b" \x19"),
+      ("TakerPays", b"\x64"),
+      ("TakerGets", b"\x65"),
+      ("Fee", b"\x68"),
+      ("SigningPubKey", b"\x73"),
+      ("TxnSignature", b"\x74"),
+      ("Account", b"\x81"),
+      ("Memos", b"\xf9"),
+      ("Memo", b"\xea"),
+      ("Paths", b"\x01\x12"),
+    ];
+    for (field_name, id) in expected {
+      assert_eq!(fields.get_field_id(field_name.to_string()).unwrap(), id);
+      assert_eq!(fields.parse_field_id(id), Some((field_name.to_string(), id.len())));
+    }
+    // only the prefix is consumed
+    assert_eq!(fields.parse_field_id(b"\x20\x19\x00\x1a"), Some(("OfferSequence".to_string(), 2)));
+    // truncated
+    assert_eq!(fields.parse_field_id(b"\x20"), None);
+    assert_eq!(fields.parse_field_id(b""), None);
+    // `Flags` in the 2 bytes form
+    assert_eq!(fields.parse_field_id(b"\x20\x02"), None);
+    // unknown field
+    assert_eq!(fields.parse_field_id(b"\x2f"), None);
+    // every field of a known type is found by its type code and field code, by its current name
+    for (field_name, (type_code, field_code)) in &fields.sort_keys {
+      if current_name(field_name).is_some() {
+        continue;
+      }
+      assert_eq!(fields.find_field(*type_code, *field_code).map(|(name, _)| name), Some(field_name));
+    }
+  }
+
+  #[test]
+  fn test_serialize_into() {
+    let fields = DefinitionFields::new();
+    let tx: Value = from_str(ACCOUNT_DELETE).unwrap();
+    let expected = crate::serialize::serialize_tx_value(&tx, true, Some(&fields)).unwrap();
+    let mut out = BytesMut::new();
+    fields.serialize_into(&tx, true, &mut out).unwrap();
+    assert_eq!(hex::encode_upper(&out), expected);
+
+    // a fixed buffer
+    let mut buf = [0u8; 128];
+    let mut remaining = &mut buf[..];
+    fields.serialize_into(&tx, true, &mut remaining).unwrap();
+    let len = 128 - remaining.len();
+    assert_eq!(hex::encode_upper(&buf[..len]), expected);
+
+    // a fixed buffer which is too small fails instead of panicking, in a nested field too
+    let mut buf = [0u8; 16];
+    assert_eq!(fields.serialize_into(&tx, true, &mut &mut buf[..]), Err(BufferTooSmall));
+    let tx_memo = json!({"Sequence": 1, "Memos": [{"Memo": {"MemoData": "72656e74"}}]});
+    let mut buf = [0u8; 10];
+    assert_eq!(fields.serialize_into(&tx_memo, false, &mut &mut buf[..]), Err(BufferTooSmall));
+
+    let mut out = Vec::new();
+    assert_eq!(fields.serialize_into(&json!({"Sequence": 1, "Comment": "rent"}), false, &mut out), Err(UnknownField("Comment".to_string())));
+    assert_eq!(fields.serialize_into(&json!({"Sequence": -1}), false, &mut out), Err(crate::errors::RippleBinaryCodecError::FieldOutOfRange { field: "Sequence".to_string(), value: -1 }));
+    assert_eq!(fields.serialize_into(&json!([]), false, &mut out), Err(InvalidJson("transaction is not a JSON object".to_string())));
+    assert!(out.is_empty());
+  }
 }
\ No newline at end of file
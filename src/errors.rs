@@ -1,15 +1,77 @@
 use alloc::string::{String, ToString};
 use base_x;
+use serde_json::Value;
 use thiserror::Error;
+use crate::types::amount::currency_code_to_bytes;
 
 #[derive(Error, Debug, PartialEq)]
 pub enum RippleBinaryCodecError {
     #[error("decode failed, reason: {0}")]
     DecodeError(String),
+    #[error("duplicate field: {0}")]
+    DuplicateField(String),
+    #[error("trailing bytes, {remaining} bytes remaining")]
+    TrailingBytes { remaining: usize },
+    #[error("truncated blob")]
+    Truncated,
+    #[error("unknown field: {0}")]
+    UnknownField(String),
+    #[error("invalid value of field: {0}")]
+    InvalidFieldValue(String),
+    #[error("buffer too small")]
+    BufferTooSmall,
+    #[error("path set too large, {paths} paths with up to {steps} steps")]
+    PathSetTooLarge { paths: usize, steps: usize },
+    #[error("conflicting fields: {0} and {1}")]
+    ConflictingFields(String, String),
+    #[error("invalid amount of field: {0}")]
+    InvalidAmount(String),
+    #[error("missing {key} of amount field {field}")]
+    MissingAmountKey { field: String, key: String },
+    #[error("invalid currency code: {0}")]
+    InvalidCurrencyCode(String),
+    #[error("value {value} of field {field} is out of range")]
+    FieldOutOfRange { field: String, value: i128 },
+    #[error("invalid JSON, reason: {0}")]
+    InvalidJson(String),
+    #[error("field not allowed: {0}")]
+    DisallowedField(String),
+    #[error("missing field {field} of {tx_type}")]
+    MissingField { tx_type: String, field: String },
+    #[error("unknown flag: {0}")]
+    UnknownFlag(String),
+    #[error("invalid public key: {0}")]
+    InvalidPublicKey(String),
 }
 
 pub type Result<T> = core::result::Result<T, RippleBinaryCodecError>;
 
+/// Tell why `field_val` of a field of `type_name` failed to serialize.
+pub(crate) fn field_error(field_name: String, field_val: &Value, type_name: &str) -> RippleBinaryCodecError {
+    use RippleBinaryCodecError::{FieldOutOfRange, InvalidAmount, InvalidCurrencyCode, InvalidFieldValue, MissingAmountKey};
+    match type_name {
+        "Amount" => {
+            if let Some(amount) = field_val.as_object().filter(|amount| !amount.contains_key("mpt_issuance_id")) {
+                if let Some(key) = ["currency", "issuer", "value"].iter().find(|key| !amount.contains_key(**key)) {
+                    return MissingAmountKey { field: field_name, key: key.to_string() };
+                }
+            }
+            match field_val.get("currency").and_then(Value::as_str) {
+                Some(currency) if currency_code_to_bytes(currency, false).is_none() => InvalidCurrencyCode(currency.to_string()),
+                _ => InvalidAmount(field_name),
+            }
+        },
+        "UInt8" | "UInt16" | "UInt32" => {
+            let value = field_val.as_u64().map(i128::from).or_else(|| field_val.as_i64().map(i128::from));
+            match value {
+                Some(value) => FieldOutOfRange { field: field_name, value },
+                None => InvalidFieldValue(field_name),
+            }
+        },
+        _ => InvalidFieldValue(field_name),
+    }
+}
+
 impl From<base_x::DecodeError> for RippleBinaryCodecError {
     fn from(value: base_x::DecodeError) -> Self {
         Self::DecodeError(value.to_string())
@@ -0,0 +1,59 @@
+//! Transaction JSON and helpers shared by the tests of several modules.
+
+/// A signed `OfferCreate` transaction, with the `hash` which is not serialized.
+pub(crate) const OFFER_CREATE: &str = r#"{
+  "Account": "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys",
+  "Expiration": 595640108,
+  "Fee": "10",
+  "Flags": 524288,
+  "OfferSequence": 1752791,
+  "Sequence": 1752792,
+  "SigningPubKey": "03EE83BB432547885C219634A1BC407A9DB0474145D69737D09CCDC63E1DEE7FE3",
+  "TakerGets": "15000000000",
+  "TakerPays": {
+    "currency": "USD",
+    "issuer": "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B",
+    "value": "7072.8"
+  },
+  "TransactionType": "OfferCreate",
+  "TxnSignature": "30440220143759437C04F7B61F012563AFE90D8DAFC46E86035E1D965A9CED282C97D4CE02204CFD241E86F17E011298FC1A39B63386C74306A5DE047E213B0F29EFA4571C2C",
+  "hash": "73734B611DDA23D3F5F62E20A173B78AB8406AC5015094DA53F53D39B9EDB06C"
+}"#;
+
+/// An unsigned `AccountDelete` transaction.
+pub(crate) const ACCOUNT_DELETE: &str = r#"{"TransactionType":"AccountDelete","Fee":"2000000","Flags":2147483648,"Destination":"rNp5zaiaR3maZ8zALz5CWnqRYXWkeGhteS","Account":"rwEJf6YSKALUaxRhvJ1S81PPmXzWhDW8on","Sequence":23159180,"LastLedgerSequence":23164152,"SigningPubKey":"02B87CEB1507849B6473773155827C0B8C15CB311C6876FBD7FAB95F06D3E18E39"}"#;
+
+/// [`ACCOUNT_DELETE`] serialized for signing.
+pub(crate) const ACCOUNT_DELETE_BLOB: &str = "1200152280000000240161618C201B016174F86840000000001E8480732102B87CEB1507849B6473773155827C0B8C15CB311C6876FBD7FAB95F06D3E18E398114656D3E2961EFABDED0C9CDCFB39FC78D01E9A77683148EED191963FEB29D532F04958BFA087A45F742C7";
+
+/// An unsigned `TrustSet` transaction.
+pub(crate) const TRUST_SET: &str = r#"{
+  "TransactionType": "TrustSet",
+  "LimitAmount": {
+    "currency": "534F4C4F00000000000000000000000000000000",
+    "issuer": "rsoLo2S1kiGeCcn6hCUXVrCpGMWLrRrLZz",
+    "value": "10000000000"
+  },
+  "Flags": 2147614720,
+  "Account": "rGUmkyLbvqGF3hwX4qwGHdrzLdY2Qpskum",
+  "Fee": "12",
+  "Sequence": 79991857,
+  "LastLedgerSequence": 80410003,
+  "SigningPubKey": "03F5C5BB1D19EC710D3D7FAD199AF10CF8BC1D11348E5B3765C0B0B9C0BEC32879"
+}"#;
+
+/// A xorshift generator for the tests of generated values, seeded with a fixed state so that a failing case can be reproduced.
+pub(crate) struct XorShift(u64);
+
+impl XorShift {
+  pub(crate) fn new() -> Self {
+    XorShift(0x2545F4914F6CDD1D)
+  }
+
+  pub(crate) fn next_u64(&mut self) -> u64 {
+    self.0 ^= self.0 << 13;
+    self.0 ^= self.0 >> 7;
+    self.0 ^= self.0 << 17;
+    self.0
+  }
+}
@@ -0,0 +1,123 @@
+//! The named flags of each transaction type, which can be used in place of the numeric `Flags` of a transaction JSON.
+
+use serde_json::{Map, Value};
+use alloc::string::{String, ToString};
+use crate::errors::{Result, RippleBinaryCodecError::{InvalidFieldValue, UnknownFlag}};
+
+/// The flags which can be set on all transactions.
+pub const UNIVERSAL_FLAGS: [(&str, u32); 1] = [("tfFullyCanonicalSig", 0x80000000)];
+
+/// The flags of each transaction type besides [`UNIVERSAL_FLAGS`], resolved by [`resolve_flags`].
+pub const TRANSACTION_FLAGS: [(&str, &[(&str, u32)]); 9] = [
+  ("Payment", &[("tfNoDirectRipple", 0x00010000), ("tfPartialPayment", 0x00020000), ("tfLimitQuality", 0x00040000)]),
+  ("OfferCreate", &[("tfPassive", 0x00010000), ("tfImmediateOrCancel", 0x00020000), ("tfFillOrKill", 0x00040000), ("tfSell", 0x00080000)]),
+  ("TrustSet", &[
+    ("tfSetfAuth", 0x00010000), ("tfSetNoRipple", 0x00020000), ("tfClearNoRipple", 0x00040000), ("tfSetFreeze", 0x00100000),
+    ("tfClearFreeze", 0x00200000),
+  ]),
+  ("AccountSet", &[
+    ("tfRequireDestTag", 0x00010000), ("tfOptionalDestTag", 0x00020000), ("tfRequireAuth", 0x00040000), ("tfOptionalAuth", 0x00080000),
+    ("tfDisallowXRP", 0x00100000), ("tfAllowXRP", 0x00200000),
+  ]),
+  ("PaymentChannelClaim", &[("tfRenew", 0x00010000), ("tfClose", 0x00020000)]),
+  ("NFTokenMint", &[("tfBurnable", 0x00000001), ("tfOnlyXRP", 0x00000002), ("tfTrustLine", 0x00000004), ("tfTransferable", 0x00000008)]),
+  ("NFTokenCreateOffer", &[("tfSellNFToken", 0x00000001)]),
+  ("AMMDeposit", &[
+    ("tfLPToken", 0x00010000), ("tfSingleAsset", 0x00080000), ("tfTwoAsset", 0x00100000), ("tfOneAssetLPToken", 0x00200000),
+    ("tfLimitLPToken", 0x00400000), ("tfTwoAssetIfEmpty", 0x00800000),
+  ]),
+  ("AMMWithdraw", &[
+    ("tfLPToken", 0x00010000), ("tfWithdrawAll", 0x00020000), ("tfOneAssetWithdrawAll", 0x00040000), ("tfSingleAsset", 0x00080000),
+    ("tfTwoAsset", 0x00100000), ("tfOneAssetLPToken", 0x00200000), ("tfLimitLPToken", 0x00400000),
+  ]),
+];
+
+/// Get the value of a named flag of `tx_type`, or of a flag in [`UNIVERSAL_FLAGS`].
+///
+/// # Example
+///
+///```
+///use rippled_binary_codec::flags::flag_value;
+///
+///fn flag_value_example(){
+///  assert_eq!(flag_value("Payment", "tfPartialPayment"), Some(0x00020000));
+///  assert_eq!(flag_value("OfferCreate", "tfPartialPayment"), None);
+///}
+///```
+pub fn flag_value(tx_type: &str, flag_name: &str) -> Option<u32> {
+  let tx_flags = TRANSACTION_FLAGS.iter()
+    .find(|(name, _)| *name == tx_type)
+    .map_or(&[][..], |(_, flags)| *flags);
+  UNIVERSAL_FLAGS.iter().chain(tx_flags)
+    .find(|(name, _)| *name == flag_name)
+    .map(|(_, value)| *value)
+}
+
+/// Replace `Flags` given as a list of flag names, like `["tfPartialPayment", "tfNoDirectRipple"]`, by the OR of their values,
+/// looked up by the `TransactionType` of `tx`. A numeric `Flags` is left unchanged.
+///
+/// # Example
+///
+///```
+///use rippled_binary_codec::flags::resolve_flags;
+///use serde_json::json;
+///
+///fn resolve_flags_example(){
+///  let mut tx = json!({"TransactionType": "Payment", "Flags": ["tfPartialPayment", "tfNoDirectRipple"]});
+///  resolve_flags(&mut tx).unwrap();
+///  assert_eq!(tx["Flags"], 0x00030000);
+///}
+///```
+///
+/// # Errors
+/// - [`UnknownFlag`][`crate::errors::RippleBinaryCodecError::UnknownFlag`] if a flag is not a flag of the transaction type.
+/// - [`InvalidFieldValue`][`crate::errors::RippleBinaryCodecError::InvalidFieldValue`] if a flag in the list is not a string.
+pub fn resolve_flags(tx: &mut Value) -> Result<()> {
+  if let Some(tx) = tx.as_object_mut() {
+    if let Some(value) = named_flags_value(tx)? {
+      tx.insert("Flags".to_string(), Value::from(value));
+    }
+  }
+  Ok(())
+}
+
+/// The value of `Flags` given as a list of flag names, see [`resolve_flags`], or `None` if it's not a list.
+pub(crate) fn named_flags_value(tx: &Map<String, Value>) -> Result<Option<u32>> {
+  let flag_names = match tx.get("Flags") {
+    Some(Value::Array(flag_names)) => flag_names,
+    _ => return Ok(None),
+  };
+  let tx_type = tx.get("TransactionType").and_then(Value::as_str).unwrap_or_default();
+  let mut value = 0u32;
+  for flag_name in flag_names {
+    let flag_name = flag_name.as_str().ok_or_else(|| InvalidFieldValue("Flags".to_string()))?;
+    value |= flag_value(tx_type, flag_name).ok_or_else(|| UnknownFlag(flag_name.to_string()))?;
+  }
+  Ok(Some(value))
+}
+
+#[cfg(test)]
+mod tests {
+  use serde_json::json;
+  use super::*;
+
+  #[test]
+  fn test_resolve_flags() {
+    let mut tx = json!({"TransactionType": "Payment", "Flags": ["tfPartialPayment", "tfNoDirectRipple", "tfFullyCanonicalSig"]});
+    assert_eq!(resolve_flags(&mut tx), Ok(()));
+    assert_eq!(tx["Flags"], 2147680256u32);
+
+    let mut tx = json!({"TransactionType": "OfferCreate", "Flags": []});
+    assert_eq!(resolve_flags(&mut tx), Ok(()));
+    assert_eq!(tx["Flags"], 0);
+
+    let mut tx = json!({"TransactionType": "Payment", "Flags": 524288});
+    assert_eq!(resolve_flags(&mut tx), Ok(()));
+    assert_eq!(tx["Flags"], 524288);
+
+    let mut tx = json!({"TransactionType": "OfferCreate", "Flags": ["tfPartialPayment"]});
+    assert_eq!(resolve_flags(&mut tx), Err(UnknownFlag("tfPartialPayment".to_string())));
+    let mut tx = json!({"TransactionType": "Payment", "Flags": [131072]});
+    assert_eq!(resolve_flags(&mut tx), Err(InvalidFieldValue("Flags".to_string())));
+  }
+}
@@ -0,0 +1,117 @@
+//! Hashes of serialized objects, like the transaction id which identifies a transaction on the ledger.
+
+use alloc::string::String;
+use cryptoxide::hashing;
+use crate::ripple_address_codec::decode_account_id;
+
+/// The prefix of a signed transaction when computing its id, `TXN\0`.
+pub const TRANSACTION_ID_PREFIX: [u8; 4] = [0x54, 0x58, 0x4E, 0x00];
+/// The prefix of a transaction to be signed by a single signer, `STX\0`.
+pub const SINGLE_SIGNING_PREFIX: [u8; 4] = [0x53, 0x54, 0x58, 0x00];
+/// The prefix of a transaction to be signed by one of multiple signers, `SMT\0`.
+pub const MULTI_SIGNING_PREFIX: [u8; 4] = [0x53, 0x4D, 0x54, 0x00];
+
+/// Return the first half of the SHA-512 of `data`, the hash function used by the XRP Ledger.
+pub fn sha512_half(data: &[u8]) -> [u8; 32] {
+  let mut half = [0u8; 32];
+  half.copy_from_slice(&hashing::sha512(data)[..32]);
+  half
+}
+
+/// Compute the transaction id, aka the `hash` field, of a signed transaction serialized by [`serialize_tx`][`crate::serialize::serialize_tx`]
+/// with `for_signing` = false.
+///
+/// The id is the [`sha512_half`] of [`TRANSACTION_ID_PREFIX`] followed by the signed transaction.
+///
+/// # Example
+///
+///```
+///use rippled_binary_codec::hashing::transaction_id;
+///
+///fn transaction_id_example(){
+///  let signed_blob = "120007220008000024001ABED82A2380BF2C2019001ABED764D55920AC9391400000000000000000000000000055534400000000000A20B3C85F482532A9578DBB3950B85CA06594D165400000037E11D60068400000000000000A732103EE83BB432547885C219634A1BC407A9DB0474145D69737D09CCDC63E1DEE7FE3744630440220143759437C04F7B61F012563AFE90D8DAFC46E86035E1D965A9CED282C97D4CE02204CFD241E86F17E011298FC1A39B63386C74306A5DE047E213B0F29EFA4571C2C8114DD76483FACDEE26E60D8A586BB58D09F27045C46";
+///  let id = transaction_id(signed_blob);
+///  println!("transaction id: {:?}", id); // Some("73734B611DDA23D3F5F62E20A173B78AB8406AC5015094DA53F53D39B9EDB06C")
+///}
+///```
+///
+/// # Errors
+///  If `signed_blob_hex` is not a valid hex string, `None` will be returned.
+pub fn transaction_id(signed_blob_hex: &str) -> Option<String> {
+  prefixed_hash(&TRANSACTION_ID_PREFIX, signed_blob_hex, &[])
+}
+
+/// Compute the hash to be signed by a single signer, of a transaction serialized by [`serialize_tx`][`crate::serialize::serialize_tx`]
+/// with `for_signing` = true.
+///
+/// The hash is the [`sha512_half`] of [`SINGLE_SIGNING_PREFIX`] followed by the transaction.
+///
+/// # Example
+///
+///```
+///use rippled_binary_codec::hashing::signing_hash;
+///
+///fn signing_hash_example(){
+///  let for_signing_blob = "12001422800200002404C49431201B04CAF59363D7038D7EA4C68000534F4C4F000000000000000000000000000000001EB3EAA3AD86242E1D51DC502DD6566BD39E06A668400000000000000C732103F5C5BB1D19EC710D3D7FAD199AF10CF8BC1D11348E5B3765C0B0B9C0BEC328798114A6C3D314FB5418627AB22D9DDF6C18AED5F6CA89";
+///  let hash = signing_hash(for_signing_blob);
+///  println!("signing hash: {:?}", hash); // Some("1872C9D003C82BFE1664F0D8D58014B9344893FAC3DE16825FF488F3D9B567E0")
+///}
+///```
+///
+/// # Errors
+///  If `for_signing_blob_hex` is not a valid hex string, `None` will be returned.
+pub fn signing_hash(for_signing_blob_hex: &str) -> Option<String> {
+  prefixed_hash(&SINGLE_SIGNING_PREFIX, for_signing_blob_hex, &[])
+}
+
+/// Compute the hash to be signed by `signer_account`, one of the signers of a multi-signed transaction serialized by
+/// [`serialize_for_multisign`][`crate::serialize::serialize_for_multisign`].
+///
+/// The hash is the [`sha512_half`] of [`MULTI_SIGNING_PREFIX`] followed by the transaction and the account id of the signer.
+///
+/// # Errors
+///  If `for_signing_blob_hex` is not a valid hex string or `signer_account` is not a valid address, `None` will be returned.
+pub fn multisigning_hash(for_signing_blob_hex: &str, signer_account: &str) -> Option<String> {
+  let account_id = decode_account_id(signer_account).ok()?;
+  prefixed_hash(&MULTI_SIGNING_PREFIX, for_signing_blob_hex, &account_id)
+}
+
+fn prefixed_hash(prefix: &[u8], blob_hex: &str, suffix: &[u8]) -> Option<String> {
+  let blob = hex::decode(blob_hex).ok()?;
+  let mut data = prefix.to_vec();
+  data.extend_from_slice(&blob);
+  data.extend_from_slice(suffix);
+  Some(hex::encode_upper(sha512_half(&data)))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use alloc::string::ToString;
+  use crate::fixtures::{OFFER_CREATE, TRUST_SET};
+  use crate::serialize::{serialize_for_multisign, serialize_tx};
+
+  #[test]
+  fn test_transaction_id() {
+    let signed_blob = serialize_tx(OFFER_CREATE.to_string(), false, None).unwrap();
+    assert_eq!(transaction_id(&signed_blob).unwrap(), "73734B611DDA23D3F5F62E20A173B78AB8406AC5015094DA53F53D39B9EDB06C");
+    assert_eq!(transaction_id("12000"), None);
+    assert_eq!(transaction_id("ZZ"), None);
+  }
+
+  #[test]
+  fn test_signing_hash() {
+    // the transaction of `test_serialize_tx` in the serialize module
+    let for_signing_blob = serialize_tx(TRUST_SET.to_string(), true, None).unwrap();
+    assert_eq!(signing_hash(&for_signing_blob).unwrap(), "1872C9D003C82BFE1664F0D8D58014B9344893FAC3DE16825FF488F3D9B567E0");
+    assert_eq!(signing_hash("ZZ"), None);
+  }
+
+  #[test]
+  fn test_multisigning_hash() {
+    let for_signing_blob = serialize_for_multisign(TRUST_SET, None).unwrap();
+    let hash = multisigning_hash(&for_signing_blob, "rsoLo2S1kiGeCcn6hCUXVrCpGMWLrRrLZz").unwrap();
+    assert_eq!(hash, "E876DEECFF9A343B7ECBF65B17780C46349668D31644EF8122A50A355C01B4FF");
+    assert_eq!(multisigning_hash(&for_signing_blob, "rsoLo2S1kiGeCcn6hCUXVrCpGMWLrRrLZy"), None);
+  }
+}
@@ -7,9 +7,76 @@ extern crate core;
 #[cfg(test)]
 #[macro_use]
 extern crate std;
+#[cfg(all(feature = "std", not(test)))]
+extern crate std;
 
 pub mod definition_fields;
 pub mod types;
 pub mod serialize;
 pub mod errors;
 pub mod ripple_address_codec;
+pub mod validate;
+pub mod hashing;
+pub mod flags;
+#[cfg(test)]
+mod fixtures;
+#[cfg(feature = "transaction")]
+pub mod transaction;
+
+pub use definition_fields::{DefinitionFields, FieldReport};
+pub use errors::RippleBinaryCodecError;
+pub use hashing::{multisigning_hash, signing_hash, transaction_id};
+pub use ripple_address_codec::{CodecConfig, SeedType, decode_account_id, decode_seed, encode_account_id, encode_seed, is_valid_classic_address};
+pub use serialize::{
+  BlobKind, HexCase, ParsedTransaction, SerializeOptions, SerializeStats, UNKNOWN_FIELDS_KEY, classify_blob, deserialize_tx, format_blob, deserialize_tx_preserving_unknown, parse_tx_strict, serialize_for_multisign, serialize_tx, serialize_tx_bytes,
+  serialize_tx_collect_errors, serialize_tx_in_order, serialize_tx_value, serialize_tx_with_options, serialize_tx_with_stats, signing_blob, validate_blob_length,
+};
+#[cfg(feature = "std")]
+pub use serialize::serialize_from_reader;
+#[cfg(feature = "lenient")]
+pub use serialize::serialize_tx_lenient;
+pub use validate::{check_allowed_fields, validate_tx};
+pub use flags::resolve_flags;
+
+#[cfg(test)]
+mod tests {
+  use alloc::string::{String, ToString};
+  use crate::fixtures::ACCOUNT_DELETE;
+  use crate::{
+    CodecConfig, DefinitionFields, RippleBinaryCodecError, SerializeOptions, SerializeStats, decode_account_id, deserialize_tx,
+    encode_account_id, parse_tx_strict, serialize_tx, serialize_tx_bytes, serialize_tx_collect_errors, serialize_tx_value,
+    serialize_tx_with_options, serialize_tx_with_stats, validate_blob_length, validate_tx,
+  };
+
+  #[test]
+  fn test_root_exports() {
+    let input = ACCOUNT_DELETE;
+    let definition_fields = DefinitionFields::new();
+    let options = SerializeOptions { for_signing: true, ..Default::default() };
+    let blob = serialize_tx(input.to_string(), true, Some(&definition_fields)).unwrap();
+    let tx = parse_tx_strict(input).unwrap();
+    assert_eq!(serialize_tx_value(&tx, true, None), Some(blob.clone()));
+    assert_eq!(serialize_tx_with_options(input.to_string(), &options, None), Ok(blob.clone()));
+    assert_eq!(serialize_tx_collect_errors(input.to_string(), true, None), Ok(blob.clone()));
+    assert_eq!(hex::encode_upper(serialize_tx_bytes(input.to_string(), &options, None).unwrap()), blob);
+    let (_, stats): (String, SerializeStats) = serialize_tx_with_stats(input.to_string(), &options, None).unwrap();
+    assert_eq!(stats.byte_length * 2, blob.len());
+    assert_eq!(validate_blob_length(&blob, None), Ok(()));
+    assert_eq!(deserialize_tx(blob, None), Some(tx.clone()));
+    assert_eq!(validate_tx(&tx, &definition_fields), Ok(()));
+
+    let account_id = decode_account_id("rwEJf6YSKALUaxRhvJ1S81PPmXzWhDW8on").unwrap();
+    assert_eq!(encode_account_id(&account_id), "rwEJf6YSKALUaxRhvJ1S81PPmXzWhDW8on");
+    assert_eq!(CodecConfig::default().encode_account_id(&account_id), "rwEJf6YSKALUaxRhvJ1S81PPmXzWhDW8on");
+    let error: RippleBinaryCodecError = parse_tx_strict("{").unwrap_err();
+    assert!(matches!(error, RippleBinaryCodecError::InvalidJson(_)));
+  }
+
+  #[test]
+  fn test_types_exports() {
+    use crate::types::{vl_decode, vl_encode};
+    let encoded = vl_encode(vec![0xab; 193]).unwrap();
+    assert_eq!(encoded[..2], [0xc1, 0x00]);
+    assert_eq!(vl_decode(&encoded), Some((vec![0xab; 193], 195)));
+  }
+}
@@ -4,7 +4,7 @@ use base_x;
 use crate::errors::RippleBinaryCodecError::DecodeError;
 use cryptoxide::hashing;
 use alloc::vec::Vec;
-use alloc::string::ToString;
+use alloc::string::{String, ToString};
 
 const CHECKSUM_LENGTH: usize = 4;
 const ALPHABET: &str = "rpshnaf39wBUDNEGHJKLM4PQRST7VWXYZ2bcdeCg65jkm8oFqi1tuvAxyz";
@@ -14,6 +14,7 @@ struct Address;
 trait Settings {
     const PAYLOAD_LEN: usize;
     const PREFIX: &'static [u8] = &[];
+    const NAME: &'static str;
 
     fn prefix(&self) -> &'static [u8] {
         Self::PREFIX
@@ -26,15 +27,127 @@ trait Settings {
     fn payload_len(&self) -> usize {
         Self::PAYLOAD_LEN
     }
+
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
 }
 
 impl Settings for Address {
     const PAYLOAD_LEN: usize = 20;
     const PREFIX: &'static [u8] = &[0x00];
+    const NAME: &'static str = "account id";
+}
+
+struct Secp256k1Seed;
+
+impl Settings for Secp256k1Seed {
+    const PAYLOAD_LEN: usize = SEED_ENTROPY_LEN;
+    const PREFIX: &'static [u8] = &[0x21];
+    const NAME: &'static str = "secp256k1 seed";
+}
+
+struct Ed25519Seed;
+
+impl Settings for Ed25519Seed {
+    const PAYLOAD_LEN: usize = SEED_ENTROPY_LEN;
+    const PREFIX: &'static [u8] = &[0x01, 0xE1, 0x4B];
+    const NAME: &'static str = "ed25519 seed";
+}
+
+/// The prefixes, payload lengths and names of the base58check encoded data of the XRP Ledger, to tell the kind of data
+/// given by mistake in the decoding errors, like a seed given as an account.
+const KNOWN_ENCODINGS: [(&[u8], usize, &str); 5] = [
+    (Address::PREFIX, Address::PAYLOAD_LEN, Address::NAME),
+    (Secp256k1Seed::PREFIX, Secp256k1Seed::PAYLOAD_LEN, Secp256k1Seed::NAME),
+    (Ed25519Seed::PREFIX, Ed25519Seed::PAYLOAD_LEN, Ed25519Seed::NAME),
+    (&[0x1C], 33, "node public key"),
+    (&[0x23], 33, "account public key"),
+];
+
+/// The length of the entropy of a family seed in bytes.
+pub const SEED_ENTROPY_LEN: usize = 16;
+
+/// The key type a family seed derives, which is told by the prefix of the encoded seed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeedType {
+    /// A classic seed starting with `s`.
+    Secp256k1,
+    /// A seed starting with `sEd`.
+    Ed25519,
+}
+
+/// The alphabet and checksum of the base58check encoding of addresses.
+///
+/// [`CodecConfig::default()`] is the encoding of the XRP Ledger, used by [`decode_account_id`] and [`encode_account_id`].
+#[derive(Debug, Clone, Copy)]
+pub struct CodecConfig {
+    /// The 58 characters alphabet.
+    pub alphabet: &'static str,
+    /// Calculates the checksum appended to the prefixed payload.
+    pub checksum: fn(&[u8]) -> [u8; CHECKSUM_LENGTH],
+}
+
+impl Default for CodecConfig {
+    fn default() -> Self {
+        CodecConfig {
+            alphabet: ALPHABET,
+            checksum: calc_checksum,
+        }
+    }
 }
 
-fn decode_with_xrp_alphabet(s: &str) -> Result<Vec<u8>> {
-    Ok(base_x::decode(ALPHABET, s)?)
+impl CodecConfig {
+    /// Decode an account id with this alphabet and checksum, see [`decode_account_id`].
+    pub fn decode_account_id(&self, account_id: &str) -> Result<[u8; Address::PAYLOAD_LEN]> {
+        let decoded_bytes = self.decode_with_alphabet(account_id)?;
+        let payload = self.get_payload(decoded_bytes, Address)?;
+        payload.try_into().map_err(|_e| DecodeError(format!("decode_account_id failed {:?}", account_id)))
+    }
+
+    /// Encode an account id with this alphabet and checksum, see [`encode_account_id`].
+    pub fn encode_account_id(&self, account_id: &[u8; Address::PAYLOAD_LEN]) -> String {
+        self.encode_with_prefix(Address::PREFIX, account_id)
+    }
+
+    fn encode_with_prefix(&self, prefix: &[u8], payload: &[u8]) -> String {
+        let mut bytes = prefix.to_vec();
+        bytes.extend_from_slice(payload);
+        let checksum = (self.checksum)(&bytes);
+        bytes.extend_from_slice(&checksum);
+        base_x::encode(self.alphabet, &bytes)
+    }
+
+    fn decode_with_alphabet(&self, s: &str) -> Result<Vec<u8>> {
+        Ok(base_x::decode(self.alphabet, s)?)
+    }
+
+    fn get_checked_bytes(&self, mut bytes_with_checksum: Vec<u8>) -> Result<Vec<u8>> {
+        verify_checksum_length(&bytes_with_checksum)?;
+
+        //Split bytes with checksum to checked bytes and checksum
+        let checksum = bytes_with_checksum.split_off(bytes_with_checksum.len() - CHECKSUM_LENGTH);
+        let bytes = bytes_with_checksum;
+
+        self.verify_checksum(&bytes, &checksum)?;
+
+        Ok(bytes)
+    }
+
+    fn get_payload(&self, bytes: Vec<u8>, settings: impl Settings) -> Result<Vec<u8>> {
+        verify_payload_len(&bytes, settings.prefix_len(), settings.payload_len(), settings.name())?;
+        verify_prefix(settings.prefix(), &bytes, settings.name())?;
+        let checked_bytes = self.get_checked_bytes(bytes)?;
+        Ok(checked_bytes[settings.prefix_len()..].to_vec())
+    }
+
+    fn verify_checksum(&self, input: &[u8], checksum: &[u8]) -> Result<()> {
+        if (self.checksum)(input) == checksum {
+            Ok(())
+        } else {
+            Err(DecodeError("varify checksum failed".to_string()))
+        }
+    }
 }
 
 fn verify_checksum_length(bytes: &[u8]) -> Result<()> {
@@ -47,59 +160,243 @@ fn verify_checksum_length(bytes: &[u8]) -> Result<()> {
     Ok(())
 }
 
-fn verify_prefix(prefix: &[u8], bytes: &[u8]) -> Result<()> {
+fn verify_prefix(prefix: &[u8], bytes: &[u8], name: &str) -> Result<()> {
     if bytes.starts_with(prefix) {
         return Ok(());
     }
 
-    Err(DecodeError("verify prefix failed".to_string()))
+    Err(DecodeError(format!("verify prefix failed, expected {} with prefix {}, got {}", name, hex::encode_upper(prefix), describe_decoded(bytes))))
 }
 
-fn get_checked_bytes(mut bytes_with_checksum: Vec<u8>) -> Result<Vec<u8>> {
-    verify_checksum_length(&bytes_with_checksum)?;
+fn verify_payload_len(bytes: &[u8], prefix_len: usize, expected_len: usize, name: &str) -> Result<()> {
+    if bytes.len() == prefix_len + expected_len + CHECKSUM_LENGTH {
+        return Ok(());
+    }
 
-    //Split bytes with checksum to checked bytes and checksum
-    let checksum = bytes_with_checksum.split_off(bytes_with_checksum.len() - CHECKSUM_LENGTH);
-    let bytes = bytes_with_checksum;
+    Err(DecodeError(format!("verify payload length failed, expected {} of {} bytes, got {}", name, expected_len, describe_decoded(bytes))))
+}
 
-    verify_checksum(&bytes, &checksum)?;
+/// Describe the decoded bytes with checksum by their prefix and length, naming the kind of data if it's in [`KNOWN_ENCODINGS`].
+fn describe_decoded(bytes: &[u8]) -> String {
+    let known = KNOWN_ENCODINGS.iter().find(|(prefix, payload_len, _)| {
+        bytes.starts_with(prefix) && bytes.len() == prefix.len() + payload_len + CHECKSUM_LENGTH
+    });
+    match (known, bytes.first()) {
+        (Some((_, _, name)), _) => name.to_string(),
+        (None, Some(first)) => format!("{} bytes with prefix {:02X}", bytes.len(), first),
+        (None, None) => "0 bytes".to_string(),
+    }
+}
 
-    Ok(bytes)
+fn calc_checksum(bytes: &[u8]) -> [u8; CHECKSUM_LENGTH] {
+    let mut checksum = [0; CHECKSUM_LENGTH];
+    checksum.copy_from_slice(&sha256_digest(&sha256_digest(bytes))[..CHECKSUM_LENGTH]);
+    checksum
 }
 
-fn verify_payload_len(bytes: &[u8], prefix_len: usize, expected_len: usize) -> Result<()> {
-    if bytes[prefix_len..bytes.len() - CHECKSUM_LENGTH].len() == expected_len {
-        return Ok(());
-    }
+fn sha256_digest(data: &[u8]) -> Vec<u8> {
+    hashing::sha256(&data).to_vec()
+}
 
-    Err(DecodeError("verify payload length failed".to_string()))
+pub fn decode_account_id(account_id: &str) -> Result<[u8; Address::PAYLOAD_LEN]> {
+    CodecConfig::default().decode_account_id(account_id)
 }
 
-fn get_payload(bytes: Vec<u8>, settings: impl Settings) -> Result<Vec<u8>> {
-    verify_payload_len(&bytes, settings.prefix_len(), settings.payload_len())?;
-    verify_prefix(settings.prefix(), &bytes)?;
-    let checked_bytes = get_checked_bytes(bytes)?;
-    Ok(checked_bytes[settings.prefix_len()..].to_vec())
+pub fn encode_account_id(account_id: &[u8; Address::PAYLOAD_LEN]) -> String {
+    CodecConfig::default().encode_account_id(account_id)
 }
 
-fn calc_checksum(bytes: &[u8]) -> Vec<u8> {
-    sha256_digest(&sha256_digest(bytes))[..CHECKSUM_LENGTH].to_vec()
+/// Check if `address` is a valid classic address, i.e. [`decode_account_id`] succeeds on it,
+/// without building an error for an invalid one.
+///
+/// # Example
+///
+///```
+///use rippled_binary_codec::ripple_address_codec::is_valid_classic_address;
+///
+///fn is_valid_classic_address_example(){
+///  assert!(is_valid_classic_address("rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys"));
+///  assert!(!is_valid_classic_address("rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3yt"));
+///}
+///```
+pub fn is_valid_classic_address(address: &str) -> bool {
+    let bytes = match base_x::decode(ALPHABET, address) {
+        Ok(bytes) => bytes,
+        Err(_) => return false,
+    };
+    let checked_len = Address::PREFIX.len() + Address::PAYLOAD_LEN;
+    bytes.len() == checked_len + CHECKSUM_LENGTH
+        && bytes.starts_with(Address::PREFIX)
+        && calc_checksum(&bytes[..checked_len]) == bytes[checked_len..]
 }
 
-fn sha256_digest(data: &[u8]) -> Vec<u8> {
-    hashing::sha256(&data).to_vec()
+/// Encode the entropy of a family seed, e.g. `sn259rEFXrQrWyx3Q7XneWcwV6dfL` for a `Secp256k1` seed and
+/// `sEdTM1uX8pu2do5XvTnutH6HsouMaM2` for an `Ed25519` seed.
+///
+/// # Example
+///
+///```
+///use rippled_binary_codec::ripple_address_codec::{encode_seed, SeedType};
+///
+///fn encode_seed_example(){
+///  let entropy = [0xCF, 0x2D, 0xE3, 0x78, 0xFB, 0xDD, 0x7E, 0x2E, 0xE8, 0x7D, 0x48, 0x6D, 0xFB, 0x5A, 0x7B, 0xFF];
+///  assert_eq!(encode_seed(&entropy, SeedType::Secp256k1).unwrap(), "sn259rEFXrQrWyx3Q7XneWcwV6dfL");
+///}
+///```
+///
+/// # Errors
+///  The encoding itself can not fail, the `Result` is kept for the symmetry with [`decode_seed`].
+pub fn encode_seed(entropy: &[u8; SEED_ENTROPY_LEN], kind: SeedType) -> Result<String> {
+    let prefix = match kind {
+        SeedType::Secp256k1 => Secp256k1Seed::PREFIX,
+        SeedType::Ed25519 => Ed25519Seed::PREFIX,
+    };
+    Ok(CodecConfig::default().encode_with_prefix(prefix, entropy))
 }
 
-fn verify_checksum(input: &[u8], checksum: &[u8]) -> Result<()> {
-    if calc_checksum(input) == checksum {
-        Ok(())
+/// Decode a family seed to its entropy and [`SeedType`], the inverse of [`encode_seed`].
+///
+/// # Example
+///
+///```
+///use rippled_binary_codec::ripple_address_codec::{decode_seed, SeedType};
+///
+///fn decode_seed_example(){
+///  let (entropy, kind) = decode_seed("sEdTM1uX8pu2do5XvTnutH6HsouMaM2").unwrap();
+///  assert_eq!(kind, SeedType::Ed25519);
+///  assert_eq!(entropy[0], 0x4C);
+///}
+///```
+///
+/// # Errors
+///  [`DecodeError`][`crate::errors::RippleBinaryCodecError::DecodeError`] will be returned if the seed is not valid base58,
+///  has an unknown prefix, a wrong length or a wrong checksum.
+pub fn decode_seed(seed: &str) -> Result<([u8; SEED_ENTROPY_LEN], SeedType)> {
+    let config = CodecConfig::default();
+    let decoded_bytes = config.decode_with_alphabet(seed)?;
+    let (payload, kind) = if decoded_bytes.starts_with(Ed25519Seed::PREFIX) {
+        (config.get_payload(decoded_bytes, Ed25519Seed)?, SeedType::Ed25519)
     } else {
-        Err(DecodeError("varify checksum failed".to_string()))
-    }
+        (config.get_payload(decoded_bytes, Secp256k1Seed)?, SeedType::Secp256k1)
+    };
+    let entropy = payload.try_into().map_err(|_e| DecodeError(format!("decode_seed failed {:?}", seed)))?;
+    Ok((entropy, kind))
 }
 
-pub fn decode_account_id(account_id: &str) -> Result<[u8; Address::PAYLOAD_LEN]> {
-    let decoded_bytes = decode_with_xrp_alphabet(account_id)?;
-    let payload = get_payload(decoded_bytes, Address)?;
-    payload.try_into().map_err(|_e| DecodeError(format!("decode_account_id failed {:?}", account_id)))
-}
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::XorShift;
+
+    #[test]
+    fn test_account_id_round_trip() {
+        let config = CodecConfig::default();
+        let mut rng = XorShift::new();
+        for _ in 0..1000 {
+            let mut account_id = [0u8; Address::PAYLOAD_LEN];
+            for byte in account_id.iter_mut() {
+                *byte = rng.next_u64() as u8;
+            }
+            let address = config.encode_account_id(&account_id);
+            assert!(address.starts_with('r'));
+            assert_eq!(config.decode_account_id(&address), Ok(account_id));
+            assert_eq!(decode_account_id(&address), Ok(account_id));
+        }
+        assert_eq!(encode_account_id(&[0u8; Address::PAYLOAD_LEN]), "rrrrrrrrrrrrrrrrrrrrrhoLvTp");
+    }
+
+    #[test]
+    fn test_decode_account_id_too_short() {
+        for address in ["", "r", "rr", "rrrr", "rrrrrr"] {
+            assert!(decode_account_id(address).is_err());
+        }
+    }
+
+    #[test]
+    fn test_decode_account_id_wrong_kind() {
+        let wrong_kind = |got: &str| Err(DecodeError(format!("verify payload length failed, expected account id of 20 bytes, got {}", got)));
+        assert_eq!(decode_account_id("sn259rEFXrQrWyx3Q7XneWcwV6dfL"), wrong_kind("secp256k1 seed"));
+        assert_eq!(decode_account_id("sEdTM1uX8pu2do5XvTnutH6HsouMaM2"), wrong_kind("ed25519 seed"));
+        let config = CodecConfig::default();
+        let node_public_key = config.encode_with_prefix(&[0x1C], &[0x02; 33]);
+        assert_eq!(decode_account_id(&node_public_key), wrong_kind("node public key"));
+        assert_eq!(decode_account_id("rrrr"), wrong_kind("4 bytes with prefix 00"));
+        assert_eq!(decode_account_id(""), wrong_kind("0 bytes"));
+
+        // the right length with another prefix
+        let address = config.encode_with_prefix(&[0x23], &[0x01; 20]);
+        let expected = "verify prefix failed, expected account id with prefix 00, got 25 bytes with prefix 23";
+        assert_eq!(decode_account_id(&address), Err(DecodeError(expected.to_string())));
+        // an account is not a seed
+        let expected = "verify payload length failed, expected secp256k1 seed of 16 bytes, got account id";
+        assert_eq!(decode_seed("rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys"), Err(DecodeError(expected.to_string())));
+    }
+
+    #[test]
+    fn test_is_valid_classic_address() {
+        let addresses = [
+            "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys",
+            "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B",
+            "rrrrrrrrrrrrrrrrrrrrrhoLvTp",
+            // wrong checksum
+            "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3yt",
+            // not in the alphabet
+            "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3y0",
+            // a seed
+            "sn259rEFXrQrWyx3Q7XneWcwV6dfL",
+            "",
+            "r",
+        ];
+        for address in addresses {
+            assert_eq!(is_valid_classic_address(address), decode_account_id(address).is_ok(), "{}", address);
+        }
+        assert!(is_valid_classic_address(addresses[0]));
+        assert!(!is_valid_classic_address(addresses[3]));
+    }
+
+    #[test]
+    fn test_seed_round_trip() {
+        let secp256k1: [u8; SEED_ENTROPY_LEN] = hex::decode("CF2DE378FBDD7E2EE87D486DFB5A7BFF").unwrap().try_into().unwrap();
+        let ed25519: [u8; SEED_ENTROPY_LEN] = hex::decode("4C3A1D213FBDFB14C7C28D609469B341").unwrap().try_into().unwrap();
+        assert_eq!(encode_seed(&secp256k1, SeedType::Secp256k1), Ok("sn259rEFXrQrWyx3Q7XneWcwV6dfL".to_string()));
+        assert_eq!(encode_seed(&ed25519, SeedType::Ed25519), Ok("sEdTM1uX8pu2do5XvTnutH6HsouMaM2".to_string()));
+        assert_eq!(decode_seed("sn259rEFXrQrWyx3Q7XneWcwV6dfL"), Ok((secp256k1, SeedType::Secp256k1)));
+        assert_eq!(decode_seed("sEdTM1uX8pu2do5XvTnutH6HsouMaM2"), Ok((ed25519, SeedType::Ed25519)));
+        for entropy in [[0u8; SEED_ENTROPY_LEN], [0xff; SEED_ENTROPY_LEN]] {
+            for kind in [SeedType::Secp256k1, SeedType::Ed25519] {
+                let seed = encode_seed(&entropy, kind).unwrap();
+                assert_eq!(decode_seed(&seed), Ok((entropy, kind)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_seed_invalid() {
+        // wrong checksums
+        assert_eq!(decode_seed("sn259rEFXrQrWyx3Q7XneWcwV6dfM"), Err(DecodeError("varify checksum failed".to_string())));
+        assert_eq!(decode_seed("sEdTM1uX8pu2do5XvTnutH6HsouMaM3"), Err(DecodeError("varify checksum failed".to_string())));
+        // an account id is not a seed
+        assert!(decode_seed("rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys").is_err());
+        assert!(decode_seed("s").is_err());
+        assert!(decode_seed("sEd0").is_err());
+    }
+
+    #[test]
+    fn test_custom_codec_config() {
+        fn xor_checksum(bytes: &[u8]) -> [u8; CHECKSUM_LENGTH] {
+            let mut checksum = [0; CHECKSUM_LENGTH];
+            for (i, byte) in bytes.iter().enumerate() {
+                checksum[i % CHECKSUM_LENGTH] ^= byte;
+            }
+            checksum
+        }
+        let config = CodecConfig {
+            alphabet: "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz",
+            checksum: xor_checksum,
+        };
+        let account_id = decode_account_id("rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys").unwrap();
+        let address = config.encode_account_id(&account_id);
+        assert_eq!(config.decode_account_id(&address), Ok(account_id));
+        assert!(decode_account_id(&address).is_err());
+    }
+}
@@ -1,11 +1,26 @@
 //! The core function to serialize the ripple transaction.
-use bytes::BytesMut;
-use serde_json::{Value, from_str};
+use core::cell::RefCell;
+use core::convert::{TryFrom, TryInto};
+use core::fmt;
+use bytes::{Bytes, BytesMut};
+use serde::de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor};
+use serde_json::{Map, Value, from_str, json};
 use hex;
-use crate::definition_fields::DefinitionFields;
+use crate::definition_fields::{DefinitionFields, FieldWriter};
+use crate::ripple_address_codec::encode_account_id;
+use crate::types::vl_decode;
+use crate::types::account::{EMPTY_ACCOUNT_FIELDS, vl_prefix};
+use crate::types::amount::{Amount, currency_code_from_bytes};
+use crate::types::issue::Issue;
+use crate::validate::{check_allowed_fields, validate_tx};
+use crate::errors::{Result, RippleBinaryCodecError, RippleBinaryCodecError::{DecodeError, DuplicateField, InvalidFieldValue, InvalidJson, TrailingBytes, Truncated, UnknownField}};
+use alloc::collections::BTreeSet;
 use alloc::string::{ToString, String};
 use alloc::vec::Vec;
 
+/// The maximum depth of nested `STObject`/`STArray` fields accepted in a blob, the same as rippled.
+const MAX_NESTING_DEPTH: usize = 10;
+
 /// The function serialize_tx takes a transaction JSON and returns a bytes object representing
 /// the transaction in binary format.
 /// Each `Field` is serialized by specific `field_to_bytes` defined in [`DefinitionFields`].
@@ -46,40 +61,1096 @@ use alloc::vec::Vec;
 /// # Errors
 /// This serialization can fail either because the input json can not deserialize to [`serde_json::Value`][`Value`] or it's not a valid XRP transaction data. If it fails, `None` will be returned.
 ///
+/// It's a hex wrapper of [`serialize_tx_bytes`], which is preferred if the serialized bytes are hashed or sent without hex encoding.
 pub fn serialize_tx(tx: String, for_signing: bool, definition_fields: Option<&DefinitionFields>) -> Option<String> {
+  let options = SerializeOptions { for_signing, ..Default::default() };
+  let fields_as_bytes = serialize_tx_bytes(tx, &options, definition_fields)?;
+  Some(hex::encode_upper(fields_as_bytes))
+}
+
+/// Options of [`serialize_tx_with_options`].
+#[derive(Debug, Clone, Default)]
+pub struct SerializeOptions {
+  /// Only serialize the signing fields, the same as `for_signing` of [`serialize_tx`].
+  pub for_signing: bool,
+  /// Run [`validate_tx`] on the transaction before serializing it.
+  pub validate: bool,
+  /// Refuse to serialize a transaction containing a field which is not in the set, including the fields nested in
+  /// `STObject` and `STArray`, see [`check_allowed_fields`]. A hardware wallet sets it to the fields it can display.
+  pub allowed_fields: Option<BTreeSet<String>>,
+  /// Serialize the raw fields under [`UNKNOWN_FIELDS_KEY`], kept by [`deserialize_tx_preserving_unknown`], in their canonical
+  /// positions, so that a blob with the fields of a newer amendment survives a round trip. The unknown fields are serialized
+  /// even if `for_signing` is set, since whether they are signing fields is unknown.
+  pub preserve_unknown: bool,
+  /// Skip the top level fields which are not in [`DefinitionFields`], such as the bookkeeping keys added by a client, instead
+  /// of failing with [`UnknownField`][`crate::errors::RippleBinaryCodecError::UnknownField`]. The skipped fields are reported
+  /// by [`serialize_tx_with_stats`].
+  pub skip_unknown: bool,
+  /// Check that the transaction has the fields required by its transaction type, see
+  /// [`validate_required`][`DefinitionFields::validate_required`].
+  pub check_required: bool,
+  /// The case of the returned hex, uppercase by default like rippled.
+  pub hex_case: HexCase,
+  /// Prefix the returned hex with `0x`.
+  pub hex_prefix: bool,
+}
+
+/// The case of the hex digits of a serialized blob, see [`format_blob`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HexCase {
+  /// `"120015"`, the format of rippled and the default.
+  Upper,
+  /// `"12000f"`, for comparing with the tools printing lowercase hex.
+  Lower,
+}
+
+impl Default for HexCase {
+  fn default() -> Self {
+    HexCase::Upper
+  }
+}
+
+/// Format serialized bytes as hex in `case`, with a `0x` prefix if `prefix` is set.
+///
+/// # Example
+///
+/// ```
+/// use rippled_binary_codec::serialize::{format_blob, HexCase};
+///
+/// fn format_blob_example(){
+///   assert_eq!(format_blob(&[0x12, 0x00, 0x0a], HexCase::Upper, false), "12000A");
+///   assert_eq!(format_blob(&[0x12, 0x00, 0x0a], HexCase::Lower, true), "0x12000a");
+/// }
+/// ```
+pub fn format_blob(bytes: &[u8], case: HexCase, prefix: bool) -> String {
+  let hex = match case {
+    HexCase::Upper => hex::encode_upper(bytes),
+    HexCase::Lower => hex::encode(bytes),
+  };
+  if prefix {
+    format!("0x{}", hex)
+  } else {
+    hex
+  }
+}
+
+/// Like [`serialize_tx`], but configured by [`SerializeOptions`] and reports why the serialization failed.
+///
+/// # Example
+///
+/// ```
+/// use rippled_binary_codec::serialize::{serialize_tx_with_options, SerializeOptions};
+///
+/// fn serialize_tx_with_options_example(){
+///   let input = r#"{"TransactionType":"AccountDelete","Fee":"2000000","Flags":2147483648,"Destination":"rNp5zaiaR3maZ8zALz5CWnqRYXWkeGhteS","Account":"rwEJf6YSKALUaxRhvJ1S81PPmXzWhDW8on","Sequence":23159180,"LastLedgerSequence":23164152,"SigningPubKey":"02B87CEB1507849B6473773155827C0B8C15CB311C6876FBD7FAB95F06D3E18E39"}"#;
+///   let options = SerializeOptions { for_signing: true, validate: true, ..Default::default() };
+///   let serialized = serialize_tx_with_options(input.to_string(), &options, None).unwrap();
+/// }
+/// ```
+///
+/// # Errors
+/// - [`InvalidJson`][`crate::errors::RippleBinaryCodecError::InvalidJson`] if the input is not a JSON object.
+/// - [`DisallowedField`][`crate::errors::RippleBinaryCodecError::DisallowedField`] if `allowed_fields` is set and the transaction contains a field out of it.
+/// - The error of the failed check if `validate` is set, see [`validate_tx`].
+/// - [`MissingField`][`crate::errors::RippleBinaryCodecError::MissingField`] if `check_required` is set and a required field is missing.
+/// - [`UnknownFlag`][`crate::errors::RippleBinaryCodecError::UnknownFlag`] if `Flags` is a list of flag names and one of them is unknown,
+///   see [`resolve_flags`][`crate::flags::resolve_flags`].
+/// - The error of the first field failed to serialize, see [`serialize_tx_collect_errors`].
+pub fn serialize_tx_with_options(tx: String, options: &SerializeOptions, definition_fields: Option<&DefinitionFields>) -> Result<String> {
+  let definition_fields = match definition_fields {
+    Some(definition_fields) => definition_fields,
+    None => DefinitionFields::global(),
+  };
+  let tx: Value = from_str(&tx).map_err(|e| InvalidJson(e.to_string()))?;
+  let (fields_as_bytes, _) = serialize_with_options(tx, options, definition_fields)?;
+  Ok(format_blob(&fields_as_bytes, options.hex_case, options.hex_prefix))
+}
+
+/// Like [`serialize_tx_with_options`], but returns the serialized bytes as [`Bytes`] instead of hex,
+/// without copying them for callers working with [`Bytes`].
+///
+/// # Example
+///
+/// ```
+/// use rippled_binary_codec::serialize::{serialize_tx_bytes, SerializeOptions};
+///
+/// fn serialize_tx_bytes_example(){
+///   let input = r#"{"TransactionType":"AccountDelete","Fee":"2000000","Flags":2147483648,"Destination":"rNp5zaiaR3maZ8zALz5CWnqRYXWkeGhteS","Account":"rwEJf6YSKALUaxRhvJ1S81PPmXzWhDW8on","Sequence":23159180,"LastLedgerSequence":23164152,"SigningPubKey":"02B87CEB1507849B6473773155827C0B8C15CB311C6876FBD7FAB95F06D3E18E39"}"#;
+///   let options = SerializeOptions { for_signing: true, ..Default::default() };
+///   let serialized = serialize_tx_bytes(input.to_string(), &options, None).unwrap();
+///   assert_eq!(serialized[..3], [0x12, 0x00, 0x15]);
+/// }
+/// ```
+///
+/// # Errors
+/// If [`serialize_tx_with_options`] fails, `None` will be returned.
+pub fn serialize_tx_bytes(tx: String, options: &SerializeOptions, definition_fields: Option<&DefinitionFields>) -> Option<Bytes> {
+  let definition_fields = match definition_fields {
+    Some(definition_fields) => definition_fields,
+    None => DefinitionFields::global(),
+  };
+  let (fields_as_bytes, _) = serialize_with_options(from_str(&tx).ok()?, options, definition_fields).ok()?;
+  Some(fields_as_bytes.freeze())
+}
+
+/// Serialize the fields of a transaction JSON exactly in the order of `field_order`, instead of the canonical order of
+/// [`ordering_fields`][`DefinitionFields::ordering_fields`], to tell an ordering issue from an encoding issue when comparing
+/// with another implementation. Every serialized field of the transaction must be in `field_order`, so that the blob covers
+/// all the fields the caller sent.
+///
+/// The blob is not canonical unless `field_order` is, so it is for diagnosis only and must not be signed or submitted.
+/// `preserve_unknown` of the options is ignored.
+///
+/// # Example
+///
+/// ```
+/// use rippled_binary_codec::serialize::{serialize_tx_in_order, SerializeOptions};
+///
+/// fn serialize_tx_in_order_example(){
+///   let input = r#"{"TransactionType":"AccountSet","Sequence":5,"Fee":"12"}"#;
+///   let field_order = ["Fee".to_string(), "TransactionType".to_string(), "Sequence".to_string()];
+///   let serialized = serialize_tx_in_order(input.to_string(), &field_order, &SerializeOptions::default(), None).unwrap();
+///   assert_eq!(serialized, "68400000000000000C1200032400000005");
+/// }
+/// ```
+///
+/// # Errors
+/// - [`InvalidFieldValue`][`crate::errors::RippleBinaryCodecError::InvalidFieldValue`] if a field of `field_order` is not in the transaction,
+///   or a serialized field of the transaction is not in `field_order`.
+/// - [`UnknownField`][`crate::errors::RippleBinaryCodecError::UnknownField`] if a field of the transaction is not in the definitions.
+/// - The same errors as [`serialize_tx_with_options`] otherwise.
+pub fn serialize_tx_in_order(tx: String, field_order: &[String], options: &SerializeOptions, definition_fields: Option<&DefinitionFields>) -> Result<String> {
+  let definition_fields = match definition_fields {
+    Some(definition_fields) => definition_fields,
+    None => DefinitionFields::global(),
+  };
+  let tx: Value = from_str(&tx).map_err(|e| InvalidJson(e.to_string()))?;
+  check_options(&tx, options, definition_fields)?;
+  let tx = tx.as_object().ok_or_else(|| InvalidJson("transaction is not a JSON object".to_string()))?;
+  for field_name in tx.keys().filter(|field_name| !field_order.contains(field_name)) {
+    let definition_field = definition_fields.get_definition_field(field_name.to_string()).ok_or_else(|| UnknownField(field_name.to_string()))?;
+    if definition_field.is_serialized && (definition_field.is_signing_field || !options.for_signing) {
+      return Err(InvalidFieldValue(field_name.to_string()));
+    }
+  }
+  let mut fields_as_bytes = BytesMut::new();
+  definition_fields.write_fields(tx, Some(field_order), options.for_signing, &mut FieldWriter::new(&mut fields_as_bytes), None)?;
+  Ok(format_blob(&fields_as_bytes, options.hex_case, options.hex_prefix))
+}
+
+/// Statistics of a serialized transaction, see [`serialize_tx_with_stats`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SerializeStats {
+  /// The number of top level fields serialized.
+  pub field_count: usize,
+  /// The length of the serialized transaction in bytes.
+  pub byte_length: usize,
+  /// The maximum number of `STObject`/`STArray` a serialized field is nested in, 0 for a transaction without them.
+  pub nesting_depth: usize,
+  /// The unknown top level fields skipped with `skip_unknown` of [`SerializeOptions`], as warnings.
+  pub skipped_fields: Vec<String>,
+}
+
+/// Like [`serialize_tx_with_options`], but also returns the [`SerializeStats`] of the serialized transaction,
+/// for logging the complexity of transactions.
+///
+/// # Example
+///
+/// ```
+/// use rippled_binary_codec::serialize::{serialize_tx_with_stats, SerializeOptions};
+///
+/// fn serialize_tx_with_stats_example(){
+///   let input = r#"{"TransactionType":"AccountDelete","Fee":"2000000","Flags":2147483648,"Destination":"rNp5zaiaR3maZ8zALz5CWnqRYXWkeGhteS","Account":"rwEJf6YSKALUaxRhvJ1S81PPmXzWhDW8on","Sequence":23159180,"LastLedgerSequence":23164152,"SigningPubKey":"02B87CEB1507849B6473773155827C0B8C15CB311C6876FBD7FAB95F06D3E18E39"}"#;
+///   let options = SerializeOptions { for_signing: true, ..Default::default() };
+///   let (serialized, stats) = serialize_tx_with_stats(input.to_string(), &options, None).unwrap();
+///   assert_eq!(stats.field_count, 8);
+///   assert_eq!(stats.byte_length, serialized.len() / 2);
+/// }
+/// ```
+///
+/// # Errors
+/// If [`serialize_tx_with_options`] fails, `None` will be returned.
+pub fn serialize_tx_with_stats(tx: String, options: &SerializeOptions, definition_fields: Option<&DefinitionFields>) -> Option<(String, SerializeStats)> {
+  let definition_fields = match definition_fields {
+    Some(definition_fields) => definition_fields,
+    None => DefinitionFields::global(),
+  };
+  let (fields_as_bytes, skipped_fields) = serialize_with_options(from_str(&tx).ok()?, options, definition_fields).ok()?;
+  let (field_count, nesting_depth) = count_fields(&fields_as_bytes, definition_fields, 0).ok()?;
+  let stats = SerializeStats {
+    field_count,
+    byte_length: fields_as_bytes.len(),
+    nesting_depth,
+    skipped_fields,
+  };
+  Some((format_blob(&fields_as_bytes, options.hex_case, options.hex_prefix), stats))
+}
+
+/// Serialize the signing fields of a transaction to the raw bytes signed by a single signer, the same as
+/// [`serialize_tx`] with `for_signing` = true but without hex encoding, ready to be prefixed and hashed, see [`signing_hash`][`crate::hashing::signing_hash`].
+///
+/// # Example
+///
+/// ```
+/// use rippled_binary_codec::serialize::signing_blob;
+///
+/// fn signing_blob_example(){
+///   let input = r#"{"TransactionType":"AccountDelete","Fee":"2000000","Flags":2147483648,"Destination":"rNp5zaiaR3maZ8zALz5CWnqRYXWkeGhteS","Account":"rwEJf6YSKALUaxRhvJ1S81PPmXzWhDW8on","Sequence":23159180}"#;
+///   let blob = signing_blob(input, None).unwrap();
+///   assert_eq!(blob[..3], [0x12, 0x00, 0x15]);
+/// }
+/// ```
+///
+/// # Errors
+/// If the input is not a JSON object or not a valid XRP transaction data, `None` will be returned.
+pub fn signing_blob(tx: &str, definition_fields: Option<&DefinitionFields>) -> Option<Vec<u8>> {
+  let options = SerializeOptions { for_signing: true, ..Default::default() };
+  Some(serialize_tx_bytes(tx.to_string(), &options, definition_fields)?.to_vec())
+}
+
+/// Serialize a transaction as the base of a multi-signature, which each signer signs with its account appended.
+///
+/// The `SigningPubKey` is forced to be empty, and only the signing fields are serialized,
+/// so `TxnSignature` and `Signers` are excluded.
+///
+/// # Example
+///
+/// ```
+/// use rippled_binary_codec::serialize::serialize_for_multisign;
+///
+/// fn serialize_for_multisign_example(){
+///   let input = r#"{"TransactionType":"AccountDelete","Fee":"2000000","Flags":2147483648,"Destination":"rNp5zaiaR3maZ8zALz5CWnqRYXWkeGhteS","Account":"rwEJf6YSKALUaxRhvJ1S81PPmXzWhDW8on","Sequence":23159180,"LastLedgerSequence":23164152}"#;
+///   let serialized = serialize_for_multisign(input, None).unwrap();
+///   assert!(serialized.contains("68400000001E84807300"));
+/// }
+/// ```
+///
+/// # Errors
+/// If the input is not a JSON object or not a valid XRP transaction data, `None` will be returned.
+pub fn serialize_for_multisign(tx: &str, definition_fields: Option<&DefinitionFields>) -> Option<String> {
+  let definition_fields = match definition_fields {
+    Some(definition_fields) => definition_fields,
+    None => DefinitionFields::global(),
+  };
+  let mut tx: Value = from_str(tx).ok()?;
+  tx.as_object_mut()?.insert("SigningPubKey".to_string(), Value::from(""));
+  serialize_value(&tx, true, definition_fields)
+}
+
+/// Serialize a transaction which is already deserialized to [`serde_json::Value`][`Value`].
+///
+/// This is the same as [`serialize_tx`], and is useful when the JSON is parsed by [`parse_tx_strict`].
+///
+/// # Example
+///
+/// ```
+/// use rippled_binary_codec::serialize::{parse_tx_strict, serialize_tx_value};
+///
+/// fn serialize_tx_value_example(){
+///   let input = r#"{"TransactionType":"AccountDelete","Fee":"2000000","Flags":2147483648,"Destination":"rNp5zaiaR3maZ8zALz5CWnqRYXWkeGhteS","Account":"rwEJf6YSKALUaxRhvJ1S81PPmXzWhDW8on","Sequence":23159180,"LastLedgerSequence":23164152,"SigningPubKey":"02B87CEB1507849B6473773155827C0B8C15CB311C6876FBD7FAB95F06D3E18E39"}"#;
+///   let tx = parse_tx_strict(input).unwrap();
+///   let serialized = serialize_tx_value(&tx, true, None);
+/// }
+/// ```
+///
+/// # Errors
+/// If the input is not a valid XRP transaction data, `None` will be returned.
+pub fn serialize_tx_value(tx: &Value, for_signing: bool, definition_fields: Option<&DefinitionFields>) -> Option<String> {
+  let definition_fields = match definition_fields {
+    Some(definition_fields) => definition_fields,
+    None => DefinitionFields::global(),
+  };
+  serialize_value(tx, for_signing, definition_fields)
+}
+
+/// Like [`serialize_tx`], but reports every problem of the transaction at once instead of failing on the first one,
+/// which is useful for showing all errors of a form to the user.
+///
+/// # Example
+///
+/// ```
+/// use rippled_binary_codec::serialize::serialize_tx_collect_errors;
+/// use rippled_binary_codec::errors::RippleBinaryCodecError;
+///
+/// fn serialize_tx_collect_errors_example(){
+///   let input = r#"{"TransactionType":"Payment","Fee":"abc","Account":"rMdG3ju8pgyVh29ELPWaDuA74CpWW6Fxns","Comment":"hello"}"#;
+///   let errors = serialize_tx_collect_errors(input.to_string(), true, None).unwrap_err();
+///   println!("errors: {:?}", errors); // [UnknownField("Comment"), InvalidAmount("Fee")]
+/// }
+/// ```
+///
+/// # Errors
+/// - [`InvalidJson`][`crate::errors::RippleBinaryCodecError::InvalidJson`] if the input is not a JSON object.
+/// - [`UnknownField`][`crate::errors::RippleBinaryCodecError::UnknownField`] for each field which is not in [`definitions.json`](https://github.com/KeystoneHQ/rippled_binary_codec/blob/main/src/fixtures/definitions.json).
+/// - For each field which fails to serialize:
+///   - [`MissingAmountKey`][`crate::errors::RippleBinaryCodecError::MissingAmountKey`] if an issued `Amount` has no `currency`, `issuer` or `value`.
+///   - [`InvalidCurrencyCode`][`crate::errors::RippleBinaryCodecError::InvalidCurrencyCode`] if the `currency` of an `Amount` is invalid.
+///   - [`InvalidAmount`][`crate::errors::RippleBinaryCodecError::InvalidAmount`] for any other invalid `Amount`.
+///   - [`FieldOutOfRange`][`crate::errors::RippleBinaryCodecError::FieldOutOfRange`] if an integer does not fit the `UInt8`, `UInt16` or `UInt32` field.
+///   - [`InvalidFieldValue`][`crate::errors::RippleBinaryCodecError::InvalidFieldValue`] otherwise.
+/// - [`UnknownFlag`][`crate::errors::RippleBinaryCodecError::UnknownFlag`] if `Flags` is a list of flag names and one of them is unknown,
+///   see [`resolve_flags`][`crate::flags::resolve_flags`].
+pub fn serialize_tx_collect_errors(tx: String, for_signing: bool, definition_fields: Option<&DefinitionFields>) -> core::result::Result<String, Vec<RippleBinaryCodecError>> {
+  let definition_fields = match definition_fields {
+    Some(definition_fields) => definition_fields,
+    None => DefinitionFields::global(),
+  };
+  let tx: Value = from_str(&tx).map_err(|e| vec![InvalidJson(e.to_string())])?;
+  let fields_as_bytes = serialize_fields(&tx, for_signing, definition_fields)?;
+  Ok(hex::encode_upper(fields_as_bytes))
+}
+
+/// Deserialize a transaction JSON to [`serde_json::Value`][`Value`], rejecting any object which contains the same key twice.
+///
+/// [`serde_json`] silently keeps the last value of a duplicated key, so a transaction like `{"Fee": "12", "Fee": "1200000"}`
+/// may be displayed and signed with different values. Parse untrusted input with this function before signing it.
+///
+/// # Example
+///
+/// ```
+/// use rippled_binary_codec::serialize::parse_tx_strict;
+/// use rippled_binary_codec::errors::RippleBinaryCodecError;
+///
+/// fn parse_tx_strict_example(){
+///   let input = r#"{"TransactionType": "Payment", "Fee": "12", "Fee": "1200000"}"#;
+///   assert_eq!(parse_tx_strict(input), Err(RippleBinaryCodecError::DuplicateField("Fee".to_string())));
+/// }
+/// ```
+///
+/// # Errors
+/// [`DuplicateField`][`crate::errors::RippleBinaryCodecError::DuplicateField`] will be returned if any key is duplicated,
+/// [`InvalidJson`][`crate::errors::RippleBinaryCodecError::InvalidJson`] will be returned if the input is not a valid JSON.
+pub fn parse_tx_strict(tx: &str) -> Result<Value> {
+  let duplicate: RefCell<Option<String>> = RefCell::new(None);
+  let mut deserializer = serde_json::Deserializer::from_str(tx);
+  let parsed = UniqueKeys { duplicate: &duplicate }
+    .deserialize(&mut deserializer)
+    .and_then(|value| deserializer.end().map(|_| value));
+  match parsed {
+    Ok(value) => Ok(value),
+    Err(e) => match duplicate.into_inner() {
+      Some(field) => Err(DuplicateField(field)),
+      None => Err(InvalidJson(e.to_string())),
+    },
+  }
+}
+
+/// Check that a serialized transaction blob is consumed exactly by its fields, with no trailing or missing bytes.
+///
+/// The blob is walked field by field, using the field id prefix to look up the type of each field
+/// in [`DefinitionFields`] and the type to know the length of its content.
+///
+/// # Example
+///
+/// ```
+/// use rippled_binary_codec::serialize::validate_blob_length;
+/// use rippled_binary_codec::errors::RippleBinaryCodecError;
+///
+/// fn validate_blob_length_example(){
+///   let blob = "1200152280000000240161618C201B016174F86840000000001E8480732102B87CEB1507849B6473773155827C0B8C15CB311C6876FBD7FAB95F06D3E18E398114656D3E2961EFABDED0C9CDCFB39FC78D01E9A77683148EED191963FEB29D532F04958BFA087A45F742C7";
+///   assert_eq!(validate_blob_length(blob, None), Ok(()));
+///   assert_eq!(validate_blob_length(&blob[..blob.len() - 2], None), Err(RippleBinaryCodecError::Truncated));
+/// }
+/// ```
+///
+/// # Errors
+/// - [`TrailingBytes`][`crate::errors::RippleBinaryCodecError::TrailingBytes`] will be returned if the bytes after the last field are not a known field.
+/// - [`Truncated`][`crate::errors::RippleBinaryCodecError::Truncated`] will be returned if the last field is cut off.
+/// - [`DecodeError`][`crate::errors::RippleBinaryCodecError::DecodeError`] will be returned if the blob is not a hex string or contains malformed data.
+pub fn validate_blob_length(blob: &str, definition_fields: Option<&DefinitionFields>) -> Result<()> {
+  let definition_fields = match definition_fields {
+    Some(definition_fields) => definition_fields,
+    None => DefinitionFields::global(),
+  };
+  let bytes = hex::decode(blob).map_err(|e| DecodeError(e.to_string()))?;
+  let mut offset = 0;
+  while offset < bytes.len() {
+    match field_len(&bytes[offset..], definition_fields, 0)? {
+      Some((field_name, len)) if !is_end_marker(&field_name) => offset += len,
+      _ => return Err(TrailingBytes { remaining: bytes.len() - offset }),
+    }
+  }
+  Ok(())
+}
+
+/// What a serialized blob is, see [`classify_blob`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlobKind {
+  /// The blob has a `TransactionType` field.
+  Transaction,
+  /// The blob has a `LedgerEntryType` field.
+  LedgerEntry,
+  /// The blob has neither, or can not be parsed before one of them is found.
+  Unknown,
+}
+
+/// Tell whether a blob is a transaction or a ledger entry, so it can be routed to the matching decoder.
+///
+/// The top level fields are walked until a `TransactionType` or a `LedgerEntryType` field is found,
+/// without decoding the whole blob.
+///
+/// # Example
+///
+/// ```
+/// use rippled_binary_codec::serialize::{classify_blob, BlobKind};
+///
+/// fn classify_blob_example(){
+///   let blob = "1200152280000000240161618C201B016174F86840000000001E8480732102B87CEB1507849B6473773155827C0B8C15CB311C6876FBD7FAB95F06D3E18E398114656D3E2961EFABDED0C9CDCFB39FC78D01E9A77683148EED191963FEB29D532F04958BFA087A45F742C7";
+///   assert_eq!(classify_blob(blob, None), BlobKind::Transaction);
+/// }
+/// ```
+pub fn classify_blob(blob: &str, definition_fields: Option<&DefinitionFields>) -> BlobKind {
   let definition_fields = match definition_fields {
     Some(definition_fields) => definition_fields,
-    None => {
-      let definition_fields = DefinitionFields::new();
-      return self::serialize_tx(tx, for_signing, Some(&definition_fields));
-    }
-  };
-  let tx: Value = from_str(&tx).ok()?;
-  if let Some(tx) = tx.as_object() {
-    let keys: Vec<String> = tx.keys().map(|item| item.to_string()).collect();
-    let field_order = definition_fields.ordering_fields(keys);
-    let mut fields_as_bytes = BytesMut::with_capacity(0);
-    for field_name in field_order {
-      let is_serialized = definition_fields.get_definition_field(field_name.clone())?.is_serialized;
-      let is_signing_field = definition_fields.get_definition_field(field_name.clone())?.is_signing_field;
-      if is_serialized {
-        if for_signing && !is_signing_field {
-          continue
+    None => DefinitionFields::global(),
+  };
+  let bytes = match hex::decode(blob) {
+    Ok(bytes) => bytes,
+    Err(_) => return BlobKind::Unknown,
+  };
+  let mut offset = 0;
+  while offset < bytes.len() {
+    match field_len(&bytes[offset..], definition_fields, 0) {
+      Ok(Some((field_name, len))) => match field_name.as_str() {
+        "TransactionType" => return BlobKind::Transaction,
+        "LedgerEntryType" => return BlobKind::LedgerEntry,
+        _ => offset += len,
+      },
+      _ => return BlobKind::Unknown,
+    }
+  }
+  BlobKind::Unknown
+}
+
+/// The function deserialize_tx takes a serialized transaction in hex and returns the transaction JSON, the inverse of [`serialize_tx`].
+/// Each field id prefix is looked up in [`DefinitionFields`] to find the name and type of the field.
+///
+/// Amounts, hashes and blobs are rendered the same as rippled, e.g. `"Fee": "10"` and `"SigningPubKey": "03EE..."`.
+///
+/// # Example
+///
+/// ```
+/// use rippled_binary_codec::serialize::deserialize_tx;
+///
+/// fn deserialize_tx_example(){
+///   let input = "1200152280000000240161618C201B016174F86840000000001E8480732102B87CEB1507849B6473773155827C0B8C15CB311C6876FBD7FAB95F06D3E18E398114656D3E2961EFABDED0C9CDCFB39FC78D01E9A77683148EED191963FEB29D532F04958BFA087A45F742C7";
+///   let tx = deserialize_tx(input.to_string(), None).unwrap();
+///   assert_eq!(tx["TransactionType"], "AccountDelete");
+///   assert_eq!(tx["Destination"], "rNp5zaiaR3maZ8zALz5CWnqRYXWkeGhteS");
+/// }
+/// ```
+///
+/// # Errors
+/// If the input is not hex, contains an unknown field or is not a complete transaction, `None` will be returned.
+pub fn deserialize_tx(blob: String, definition_fields: Option<&DefinitionFields>) -> Option<Value> {
+  let definition_fields = match definition_fields {
+    Some(definition_fields) => definition_fields,
+    None => DefinitionFields::global(),
+  };
+  let bytes = hex::decode(blob).ok()?;
+  let fields = decode_fields(&bytes, definition_fields, None, 0, None).ok()?;
+  Some(Value::Object(fields.into_iter().collect()))
+}
+
+/// The key of the fields not in [`DefinitionFields`] in the transaction JSON returned by [`deserialize_tx_preserving_unknown`],
+/// an array of the raw bytes in hex of each field, including its field id prefix.
+pub const UNKNOWN_FIELDS_KEY: &str = "__unknown_fields";
+
+/// Like [`deserialize_tx`], but keeps the top level fields which are not in [`DefinitionFields`], such as the fields of
+/// an amendment newer than the definitions, under [`UNKNOWN_FIELDS_KEY`] instead of failing, so that serializing the result
+/// with [`SerializeOptions::preserve_unknown`] set gives back the same blob.
+///
+/// An unknown field can only be skipped if its type code is known, since its length depends on its type.
+///
+/// # Example
+///
+/// ```
+/// use rippled_binary_codec::serialize::{deserialize_tx_preserving_unknown, UNKNOWN_FIELDS_KEY};
+///
+/// fn deserialize_tx_preserving_unknown_example(){
+///   // an AccountDelete with a `UInt32` field of field code 99 which is not in the definitions
+///   let input = "1200152280000000240161618C201B016174F82063000000056840000000001E8480732102B87CEB1507849B6473773155827C0B8C15CB311C6876FBD7FAB95F06D3E18E398114656D3E2961EFABDED0C9CDCFB39FC78D01E9A77683148EED191963FEB29D532F04958BFA087A45F742C7";
+///   let tx = deserialize_tx_preserving_unknown(input.to_string(), None).unwrap();
+///   assert_eq!(tx[UNKNOWN_FIELDS_KEY][0], "206300000005");
+/// }
+/// ```
+///
+/// # Errors
+/// If the input is not hex, contains a field of an unknown type or is not a complete transaction, `None` will be returned.
+pub fn deserialize_tx_preserving_unknown(blob: String, definition_fields: Option<&DefinitionFields>) -> Option<Value> {
+  let definition_fields = match definition_fields {
+    Some(definition_fields) => definition_fields,
+    None => DefinitionFields::global(),
+  };
+  let bytes = hex::decode(blob).ok()?;
+  let mut unknown_fields = Vec::new();
+  let fields = decode_fields(&bytes, definition_fields, None, 0, Some(&mut unknown_fields)).ok()?;
+  let mut tx: Map<String, Value> = fields.into_iter().collect();
+  if !unknown_fields.is_empty() {
+    tx.insert(UNKNOWN_FIELDS_KEY.to_string(), Value::Array(unknown_fields));
+  }
+  Some(Value::Object(tx))
+}
+
+/// A transaction decoded from a serialized blob, converted from a hex blob by [`TryFrom`] like [`deserialize_tx`].
+///
+/// # Example
+///
+/// ```
+/// use core::convert::TryInto;
+/// use rippled_binary_codec::serialize::ParsedTransaction;
+///
+/// fn parsed_transaction_example(){
+///   let input = "1200152280000000240161618C201B016174F86840000000001E8480732102B87CEB1507849B6473773155827C0B8C15CB311C6876FBD7FAB95F06D3E18E398114656D3E2961EFABDED0C9CDCFB39FC78D01E9A77683148EED191963FEB29D532F04958BFA087A45F742C7";
+///   let tx: ParsedTransaction = input.try_into().unwrap();
+///   assert_eq!(tx.get("TransactionType").unwrap(), "AccountDelete");
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedTransaction {
+  /// The transaction JSON, the same as the output of [`deserialize_tx`].
+  pub data: Value,
+}
+
+impl ParsedTransaction {
+  /// Return the value of a field, or `None` if the transaction doesn't have the field.
+  pub fn get(&self, field_name: &str) -> Option<&Value> {
+    self.data.get(field_name)
+  }
+}
+
+impl TryFrom<&str> for ParsedTransaction {
+  type Error = RippleBinaryCodecError;
+
+  /// Decode a hex blob with [`DefinitionFields::global`].
+  ///
+  /// # Errors
+  ///  [`DecodeError`][`crate::errors::RippleBinaryCodecError::DecodeError`] if the blob is not hex, otherwise the error of the first field which fails to decode.
+  fn try_from(blob: &str) -> Result<Self> {
+    let bytes = hex::decode(blob).map_err(|e| DecodeError(e.to_string()))?;
+    let fields = decode_fields(&bytes, DefinitionFields::global(), None, 0, None)?;
+    Ok(ParsedTransaction { data: Value::Object(fields.into_iter().collect()) })
+  }
+}
+
+fn is_end_marker(field_name: &str) -> bool {
+  field_name == "ObjectEndMarker" || field_name == "ArrayEndMarker"
+}
+
+/// Return the name and the length (including the field id prefix) of the field at the start of `bytes`,
+/// or `None` if the field id is unknown. `depth` is the number of `STObject`/`STArray` the field is nested in.
+fn field_len(bytes: &[u8], definition_fields: &DefinitionFields, depth: usize) -> Result<Option<(String, usize)>> {
+  let (type_code, field_code, id_len) = DefinitionFields::read_field_id(bytes).ok_or(Truncated)?;
+  let (field_name, field) = match definition_fields.find_field(type_code, field_code) {
+    Some(field) => field,
+    None => return Ok(None),
+  };
+  let content = &bytes[id_len..];
+  let content_len = if is_end_marker(field_name) {
+    0
+  } else {
+    content_len(content, &field.type_name, field.is_vl_encoded, definition_fields, depth)?
+  };
+  if content_len > content.len() {
+    return Err(Truncated);
+  }
+  Ok(Some((field_name.clone(), id_len + content_len)))
+}
+
+/// Return the length (including the field id prefix) of a field at the start of `bytes` which is not in `definition_fields`,
+/// telling the length from its type code. Only a field of a known type can be skipped.
+fn unknown_field_len(bytes: &[u8], definition_fields: &DefinitionFields) -> Result<usize> {
+  let (type_code, _, id_len) = DefinitionFields::read_field_id(bytes).ok_or(Truncated)?;
+  let definitions = definition_fields.definitions.as_ref().ok_or_else(|| DecodeError("no definitions".to_string()))?;
+  let (type_name, _) = definitions.types.iter().find(|(_, code)| **code == type_code)
+    .ok_or_else(|| DecodeError("unknown field id".to_string()))?;
+  let is_vl_encoded = matches!(type_name.as_str(), "Blob" | "AccountID" | "Vector256");
+  let content = &bytes[id_len..];
+  let content_len = content_len(content, type_name, is_vl_encoded, definition_fields, 0)?;
+  if content_len > content.len() {
+    return Err(Truncated);
+  }
+  Ok(id_len + content_len)
+}
+
+/// Return the length of the content of a field of `type_name`, `content` is the bytes following the field id prefix.
+fn content_len(content: &[u8], type_name: &str, is_vl_encoded: bool, definition_fields: &DefinitionFields, depth: usize) -> Result<usize> {
+  if is_vl_encoded {
+    let (len, prefix_len) = vl_prefix(content)?;
+    return Ok(prefix_len + len);
+  }
+  if let Some(width) = DefinitionFields::fixed_width_of(type_name) {
+    return Ok(width);
+  }
+  match type_name {
+    "Amount" => Amount::len_of(content).ok_or(Truncated),
+    "Issue" => Issue::len_of(content).ok_or(Truncated),
+    "PathSet" => path_set_len(content),
+    "STObject" => nested_len(content, definition_fields, "ObjectEndMarker", depth + 1),
+    "STArray" => nested_len(content, definition_fields, "ArrayEndMarker", depth + 1),
+    type_name => Err(DecodeError(format!("unsupported type {}", type_name))),
+  }
+}
+
+/// Whether a type byte of a path step has one or more of the `account` (0x01), `currency` (0x10) and `issuer` (0x20) flags,
+/// and no other bits.
+fn is_path_step_type(step_type: u8) -> bool {
+  step_type != 0 && step_type & !0x31 == 0
+}
+
+fn path_set_len(bytes: &[u8]) -> Result<usize> {
+  let mut offset = 0;
+  loop {
+    let step_type = *bytes.get(offset).ok_or(Truncated)?;
+    offset += 1;
+    match step_type {
+      0x00 => return Ok(offset),
+      0xff => continue,
+      _ if !is_path_step_type(step_type) => return Err(DecodeError("invalid path step type".to_string())),
+      _ => {
+        for flag in [0x01, 0x10, 0x20] {
+          if step_type & flag != 0 {
+            offset += 20;
+          }
         }
-        let field_val =  definition_fields.get_field_by_name(tx, field_name.as_str())?;
-        let field_bytes = definition_fields.field_to_bytes(field_name, field_val)?;
-        fields_as_bytes.extend_from_slice(&field_bytes);
       }
     }
-    return Some(hex::encode(fields_as_bytes).to_uppercase());
   }
-  return None;
+}
+
+/// Return the length of the fields of an `STObject` or `STArray`, including the `end_marker`.
+fn nested_len(bytes: &[u8], definition_fields: &DefinitionFields, end_marker: &str, depth: usize) -> Result<usize> {
+  if depth > MAX_NESTING_DEPTH {
+    return Err(DecodeError("maximum nesting depth exceeded".to_string()));
+  }
+  let mut offset = 0;
+  loop {
+    let rest = bytes.get(offset..).ok_or(Truncated)?;
+    match field_len(rest, definition_fields, depth)? {
+      Some((field_name, len)) => {
+        offset += len;
+        if field_name == end_marker {
+          return Ok(offset);
+        }
+      },
+      None => return Err(DecodeError("unknown field id".to_string())),
+    }
+  }
+}
+
+/// Return the number of fields of `bytes`, excluding end markers, and the maximum depth of the fields nested in them.
+fn count_fields(bytes: &[u8], definition_fields: &DefinitionFields, depth: usize) -> Result<(usize, usize)> {
+  let mut field_count = 0;
+  let mut max_depth = depth;
+  let mut offset = 0;
+  while offset < bytes.len() {
+    let (field_name, len) = field_len(&bytes[offset..], definition_fields, depth)?
+      .ok_or_else(|| DecodeError("unknown field id".to_string()))?;
+    if !is_end_marker(&field_name) {
+      field_count += 1;
+      let field = definition_fields.get_definition_field(field_name.clone()).ok_or(UnknownField(field_name))?;
+      if field.type_name == "STObject" || field.type_name == "STArray" {
+        let (_, _, id_len) = DefinitionFields::read_field_id(&bytes[offset..]).ok_or(Truncated)?;
+        let (_, nested_depth) = count_fields(&bytes[offset + id_len..offset + len], definition_fields, depth + 1)?;
+        max_depth = max_depth.max(nested_depth);
+      }
+    }
+    offset += len;
+  }
+  Ok((field_count, max_depth))
+}
+
+/// Decode the fields of `bytes` in order. Nested fields end with `end_marker`, which must be the last field of `bytes`,
+/// while the top level fields run to the end of `bytes`.
+///
+/// The raw bytes of the fields not in `definition_fields` are pushed to `unknown_fields` in hex if it's given,
+/// otherwise an unknown field fails the decoding.
+fn decode_fields(
+  bytes: &[u8], definition_fields: &DefinitionFields, end_marker: Option<&str>, depth: usize, mut unknown_fields: Option<&mut Vec<Value>>,
+) -> Result<Vec<(String, Value)>> {
+  let mut fields = Vec::new();
+  let mut offset = 0;
+  while offset < bytes.len() {
+    let (field_name, len) = match (field_len(&bytes[offset..], definition_fields, depth)?, unknown_fields.as_deref_mut()) {
+      (Some(field), _) => field,
+      (None, Some(unknown_fields)) => {
+        let len = unknown_field_len(&bytes[offset..], definition_fields)?;
+        unknown_fields.push(Value::from(hex::encode_upper(&bytes[offset..offset + len])));
+        offset += len;
+        continue
+      },
+      (None, None) => return Err(DecodeError("unknown field id".to_string())),
+    };
+    if is_end_marker(&field_name) {
+      if end_marker == Some(field_name.as_str()) && offset + len == bytes.len() {
+        return Ok(fields);
+      }
+      return Err(DecodeError(format!("unexpected {}", field_name)));
+    }
+    let (_, id_len) = definition_fields.parse_field_id(&bytes[offset..])
+      .ok_or_else(|| DecodeError("non-canonical field id".to_string()))?;
+    let value = decode_field(&field_name, &bytes[offset + id_len..offset + len], definition_fields, depth)?;
+    fields.push((field_name, value));
+    offset += len;
+  }
+  match end_marker {
+    Some(_) => Err(Truncated),
+    None => Ok(fields),
+  }
+}
+
+/// Decode the content of a field, `content` is exactly the bytes following the field id prefix.
+fn decode_field(field_name: &str, content: &[u8], definition_fields: &DefinitionFields, depth: usize) -> Result<Value> {
+  let field = definition_fields.get_definition_field(field_name.to_string()).ok_or_else(|| UnknownField(field_name.to_string()))?;
+  let vl_content;
+  let content = if field.is_vl_encoded {
+    vl_content = vl_decode(content).ok_or(Truncated)?.0;
+    vl_content.as_slice()
+  } else {
+    content
+  };
+  let invalid = || InvalidFieldValue(field_name.to_string());
+  let value = match field.type_name.as_str() {
+    "UInt8" | "UInt16" | "UInt32" => {
+      let number = content.iter().fold(0u64, |number, byte| number << 8 | u64::from(*byte));
+      let definitions = definition_fields.definitions.as_ref().ok_or_else(invalid)?;
+      let names = match field_name {
+        "TransactionType" => Some(&definitions.transaction_types),
+        "TransactionResult" => Some(&definitions.transaction_results),
+        "LedgerEntryType" => Some(&definitions.ledger_entry_types),
+        _ => None,
+      };
+      match names {
+        Some(names) => {
+          let (name, _) = names.iter().find(|(_, code)| u64::try_from(**code) == Ok(number)).ok_or_else(invalid)?;
+          Value::from(name.as_str())
+        },
+        None => Value::from(number),
+      }
+    },
+    "UInt64" | "UInt96" | "UInt128" | "UInt160" | "UInt192" | "Hash128" | "Hash160" | "Hash192" | "Hash256" | "Blob" => {
+      Value::from(hex::encode_upper(content))
+    },
+    "AccountID" if content.is_empty() && EMPTY_ACCOUNT_FIELDS.contains(&field_name) => Value::from(""),
+    "AccountID" => {
+      let account_id = content.try_into().map_err(|_| invalid())?;
+      Value::from(encode_account_id(account_id))
+    },
+    "Vector256" => {
+      if content.len() % 32 != 0 {
+        return Err(invalid());
+      }
+      content.chunks(32).map(|hash| Value::from(hex::encode_upper(hash))).collect()
+    },
+    "Amount" => Amount::from_bytes(content).ok_or_else(invalid)?,
+    "Issue" => Issue::from_bytes(content).ok_or_else(invalid)?,
+    "PathSet" => decode_path_set(content).ok_or_else(invalid)?,
+    "STObject" => {
+      let fields = decode_fields(content, definition_fields, Some("ObjectEndMarker"), depth + 1, None)?;
+      Value::Object(fields.into_iter().collect())
+    },
+    "STArray" => {
+      let fields = decode_fields(content, definition_fields, Some("ArrayEndMarker"), depth + 1, None)?;
+      fields.into_iter().map(|(name, value)| json!({ name: value })).collect()
+    },
+    type_name => return Err(DecodeError(format!("unsupported type {}", type_name))),
+  };
+  Ok(value)
+}
+
+fn decode_path_set(bytes: &[u8]) -> Option<Value> {
+  let mut paths = Vec::new();
+  let mut path = Vec::new();
+  let mut offset = 0;
+  loop {
+    let step_type = *bytes.get(offset)?;
+    offset += 1;
+    if step_type == 0x00 || step_type == 0xFF {
+      paths.push(Value::Array(core::mem::take(&mut path)));
+      if step_type == 0x00 {
+        return Some(Value::Array(paths));
+      }
+      continue;
+    }
+    if !is_path_step_type(step_type) {
+      return None;
+    }
+    let mut step = Map::new();
+    if step_type & 0x01 != 0 {
+      step.insert("account".to_string(), Value::from(encode_account_id(bytes.get(offset..offset + 20)?.try_into().ok()?)));
+      offset += 20;
+    }
+    if step_type & 0x10 != 0 {
+      step.insert("currency".to_string(), Value::from(currency_code_from_bytes(bytes.get(offset..offset + 20)?)?));
+      offset += 20;
+    }
+    if step_type & 0x20 != 0 {
+      step.insert("issuer".to_string(), Value::from(encode_account_id(bytes.get(offset..offset + 20)?.try_into().ok()?)));
+      offset += 20;
+    }
+    path.push(Value::Object(step));
+  }
+}
+
+/// A [`DeserializeSeed`] building a [`Value`] which records the first duplicated object key it meets.
+struct UniqueKeys<'a> {
+  duplicate: &'a RefCell<Option<String>>,
+}
+
+impl<'de> DeserializeSeed<'de> for UniqueKeys<'_> {
+  type Value = Value;
+
+  fn deserialize<D>(self, deserializer: D) -> core::result::Result<Value, D::Error>
+  where
+    D: de::Deserializer<'de>,
+  {
+    deserializer.deserialize_any(self)
+  }
+}
+
+impl<'de> Visitor<'de> for UniqueKeys<'_> {
+  type Value = Value;
+
+  fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+    formatter.write_str("any valid JSON value")
+  }
+
+  fn visit_bool<E>(self, v: bool) -> core::result::Result<Value, E> {
+    Ok(Value::Bool(v))
+  }
+
+  fn visit_i64<E>(self, v: i64) -> core::result::Result<Value, E> {
+    Ok(Value::from(v))
+  }
+
+  fn visit_u64<E>(self, v: u64) -> core::result::Result<Value, E> {
+    Ok(Value::from(v))
+  }
+
+  fn visit_f64<E>(self, v: f64) -> core::result::Result<Value, E> {
+    Ok(Value::from(v))
+  }
+
+  fn visit_str<E>(self, v: &str) -> core::result::Result<Value, E> {
+    Ok(Value::String(v.to_string()))
+  }
+
+  fn visit_string<E>(self, v: String) -> core::result::Result<Value, E> {
+    Ok(Value::String(v))
+  }
+
+  fn visit_unit<E>(self) -> core::result::Result<Value, E> {
+    Ok(Value::Null)
+  }
+
+  fn visit_seq<A>(self, mut seq: A) -> core::result::Result<Value, A::Error>
+  where
+    A: SeqAccess<'de>,
+  {
+    let mut values = Vec::new();
+    while let Some(value) = seq.next_element_seed(UniqueKeys { duplicate: self.duplicate })? {
+      values.push(value);
+    }
+    Ok(Value::Array(values))
+  }
+
+  fn visit_map<A>(self, mut map: A) -> core::result::Result<Value, A::Error>
+  where
+    A: MapAccess<'de>,
+  {
+    let mut values = Map::new();
+    while let Some(key) = map.next_key::<String>()? {
+      if values.contains_key(&key) {
+        let message = format!("duplicate field `{}`", key);
+        *self.duplicate.borrow_mut() = Some(key);
+        return Err(de::Error::custom(message));
+      }
+      let value = map.next_value_seed(UniqueKeys { duplicate: self.duplicate })?;
+      values.insert(key, value);
+    }
+    Ok(Value::Object(values))
+  }
+}
+
+/// Like [`serialize_tx_with_options`], but reads the transaction JSON from a [`BufRead`][`std::io::BufRead`] source,
+/// so file-based inputs do not have to be buffered into an intermediate `String` first.
+///
+/// Only available with the `std` feature.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Cursor;
+/// use rippled_binary_codec::serialize::{serialize_from_reader, SerializeOptions};
+///
+/// fn serialize_from_reader_example(){
+///   let input = r#"{"TransactionType":"AccountDelete","Fee":"2000000","Flags":2147483648,"Destination":"rNp5zaiaR3maZ8zALz5CWnqRYXWkeGhteS","Account":"rwEJf6YSKALUaxRhvJ1S81PPmXzWhDW8on","Sequence":23159180,"LastLedgerSequence":23164152,"SigningPubKey":"02B87CEB1507849B6473773155827C0B8C15CB311C6876FBD7FAB95F06D3E18E39"}"#;
+///   let options = SerializeOptions { for_signing: true, ..Default::default() };
+///   let serialized = serialize_from_reader(Cursor::new(input), &options, None).unwrap();
+/// }
+/// ```
+///
+/// # Errors
+/// - [`InvalidJson`][`crate::errors::RippleBinaryCodecError::InvalidJson`] if the input can not be read or deserialized to [`serde_json::Value`][`Value`].
+/// - The same errors as [`serialize_tx_with_options`] otherwise.
+#[cfg(feature = "std")]
+pub fn serialize_from_reader<R: std::io::BufRead>(reader: R, options: &SerializeOptions, definition_fields: Option<&DefinitionFields>) -> Result<String> {
+  let definition_fields = match definition_fields {
+    Some(definition_fields) => definition_fields,
+    None => DefinitionFields::global(),
+  };
+  let tx: Value = serde_json::from_reader(reader).map_err(|e| InvalidJson(e.to_string()))?;
+  let (fields_as_bytes, _) = serialize_with_options(tx, options, definition_fields)?;
+  Ok(format_blob(&fields_as_bytes, options.hex_case, options.hex_prefix))
+}
+
+/// Like [`serialize_tx_with_options`], but parse the input as [JSON5](https://json5.org), which allows the comments, trailing
+/// commas and unquoted keys of a hand-written transaction. Available with the `lenient` feature.
+///
+/// # Example
+///
+/// ```
+/// use rippled_binary_codec::serialize::{serialize_tx_lenient, SerializeOptions};
+///
+/// fn serialize_tx_lenient_example(){
+///   let input = r#"{
+///     // delete the account
+///     TransactionType: "AccountDelete",
+///     Fee: "2000000",
+///   }"#;
+///   let options = SerializeOptions { for_signing: true, ..Default::default() };
+///   let serialized = serialize_tx_lenient(input.to_string(), &options, None).unwrap();
+///   assert_eq!(serialized, "1200156840000000001E8480");
+/// }
+/// ```
+///
+/// # Errors
+/// - [`InvalidJson`][`crate::errors::RippleBinaryCodecError::InvalidJson`] if the input is not valid JSON5.
+/// - The same errors as [`serialize_tx_with_options`] otherwise.
+#[cfg(feature = "lenient")]
+pub fn serialize_tx_lenient(tx: String, options: &SerializeOptions, definition_fields: Option<&DefinitionFields>) -> Result<String> {
+  let definition_fields = match definition_fields {
+    Some(definition_fields) => definition_fields,
+    None => DefinitionFields::global(),
+  };
+  let tx: Value = json5::from_str(&tx).map_err(|e| InvalidJson(e.to_string()))?;
+  let (fields_as_bytes, _) = serialize_with_options(tx, options, definition_fields)?;
+  Ok(format_blob(&fields_as_bytes, options.hex_case, options.hex_prefix))
+}
+
+/// Serialize a parsed transaction JSON by `options`, return the serialized bytes and the names of the skipped unknown fields.
+fn serialize_with_options(mut tx: Value, options: &SerializeOptions, definition_fields: &DefinitionFields) -> Result<(BytesMut, Vec<String>)> {
+  let unknown_fields = match (options.preserve_unknown, tx.as_object_mut()) {
+    (true, Some(tx)) => tx.remove(UNKNOWN_FIELDS_KEY),
+    _ => None,
+  };
+  let mut skipped_fields = Vec::new();
+  if let (true, Some(tx)) = (options.skip_unknown, tx.as_object_mut()) {
+    skipped_fields = tx.keys().filter(|field_name| !definition_fields.field_exists(field_name)).cloned().collect();
+    for field_name in &skipped_fields {
+      tx.remove(field_name);
+    }
+  }
+  check_options(&tx, options, definition_fields)?;
+  let mut fields_as_bytes = BytesMut::new();
+  definition_fields.serialize_into(&tx, options.for_signing, &mut fields_as_bytes)?;
+  let fields_as_bytes = match unknown_fields {
+    Some(unknown_fields) => insert_unknown_fields(&fields_as_bytes, &unknown_fields, definition_fields)?,
+    None => fields_as_bytes,
+  };
+  Ok((fields_as_bytes, skipped_fields))
+}
+
+/// Run the checks of `allowed_fields` and `validate` of [`SerializeOptions`].
+fn check_options(tx: &Value, options: &SerializeOptions, definition_fields: &DefinitionFields) -> Result<()> {
+  if let Some(allowed_fields) = &options.allowed_fields {
+    check_allowed_fields(tx, allowed_fields, options.for_signing, definition_fields)?;
+  }
+  if options.validate {
+    validate_tx(tx, definition_fields)?;
+  }
+  if options.check_required {
+    definition_fields.validate_required(tx)?;
+  }
+  Ok(())
+}
+
+/// Insert the raw fields kept by [`deserialize_tx_preserving_unknown`] into the serialized known fields, in the order of
+/// their type codes and field codes.
+fn insert_unknown_fields(known_fields: &[u8], unknown_fields: &Value, definition_fields: &DefinitionFields) -> Result<BytesMut> {
+  let invalid = || InvalidFieldValue(UNKNOWN_FIELDS_KEY.to_string());
+  let mut unknown = Vec::new();
+  for field in unknown_fields.as_array().ok_or_else(invalid)? {
+    let bytes = hex::decode(field.as_str().ok_or_else(invalid)?).map_err(|_| invalid())?;
+    let (type_code, field_code, _) = DefinitionFields::read_field_id(&bytes).ok_or_else(invalid)?;
+    if definition_fields.find_field(type_code, field_code).is_some() || unknown_field_len(&bytes, definition_fields) != Ok(bytes.len()) {
+      return Err(invalid());
+    }
+    unknown.push(((type_code, field_code), bytes));
+  }
+  unknown.sort_by_key(|(sort_key, _)| *sort_key);
+  let mut unknown = unknown.into_iter().peekable();
+  let mut fields_as_bytes = BytesMut::with_capacity(known_fields.len());
+  let mut offset = 0;
+  while offset < known_fields.len() {
+    let (type_code, field_code, _) = DefinitionFields::read_field_id(&known_fields[offset..]).ok_or(Truncated)?;
+    while let Some((_, bytes)) = unknown.next_if(|(sort_key, _)| *sort_key < (type_code, field_code)) {
+      fields_as_bytes.extend_from_slice(&bytes);
+    }
+    let (_, len) = field_len(&known_fields[offset..], definition_fields, 0)?.ok_or(Truncated)?;
+    fields_as_bytes.extend_from_slice(&known_fields[offset..offset + len]);
+    offset += len;
+  }
+  for (_, bytes) in unknown {
+    fields_as_bytes.extend_from_slice(&bytes);
+  }
+  Ok(fields_as_bytes)
+}
+
+fn serialize_value(tx: &Value, for_signing: bool, definition_fields: &DefinitionFields) -> Option<String> {
+  let fields_as_bytes = serialize_fields(tx, for_signing, definition_fields).ok()?;
+  Some(hex::encode_upper(fields_as_bytes))
+}
+
+/// Serialize each field of `tx` in canonical order, collecting the errors of all failed fields instead of returning on the first one.
+fn serialize_fields(tx: &Value, for_signing: bool, definition_fields: &DefinitionFields) -> core::result::Result<BytesMut, Vec<RippleBinaryCodecError>> {
+  let tx = tx.as_object().ok_or_else(|| vec![InvalidJson("transaction is not a JSON object".to_string())])?;
+  let mut fields_as_bytes = BytesMut::new();
+  let mut errors = Vec::new();
+  // the errors are collected instead of returned, so the result is always `Ok`
+  let _ = definition_fields.write_fields(tx, None, for_signing, &mut FieldWriter::new(&mut fields_as_bytes), Some(&mut errors));
+  if errors.is_empty() {
+    Ok(fields_as_bytes)
+  } else {
+    Err(errors)
+  }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::errors::RippleBinaryCodecError::{
+        ConflictingFields, DisallowedField, FieldOutOfRange, InvalidAmount, InvalidCurrencyCode, MissingAmountKey, MissingField, PathSetTooLarge, UnknownFlag,
+    };
+    use crate::fixtures::{ACCOUNT_DELETE, ACCOUNT_DELETE_BLOB, OFFER_CREATE, TRUST_SET};
     use super::*;
 
+    /// Assert that the full serialization of `input` deserializes back to `input`, except the fields not serialized.
+    fn assert_round_trip(input: &str){
+        let definition_fields = DefinitionFields::new();
+        let mut expected: Value = from_str(input).unwrap();
+        expected.as_object_mut().unwrap().retain(|field_name, _| {
+          definition_fields.get_definition_field(field_name.to_string()).unwrap().is_serialized
+        });
+        let blob = serialize_tx(input.to_string(), false, Some(&definition_fields)).unwrap();
+        assert_eq!(deserialize_tx(blob, Some(&definition_fields)), Some(expected));
+    }
+
+    /// Assert that the top level fields of `input` are in `expected_order` once sorted in canonical order.
+    fn assert_field_order(input: &str, expected_order: &[&str]){
+        let keys: Vec<String> = from_str::<Value>(input).unwrap().as_object().unwrap().keys().cloned().collect();
+        assert_eq!(DefinitionFields::global().ordering_fields(keys), expected_order);
+    }
+
     #[test]
     fn test_serialize_tx(){
       let input= r#"{
@@ -99,6 +1170,7 @@ mod tests {
       let expected= "12001422800200002404C49431201B04CAF59363D7038D7EA4C68000534F4C4F000000000000000000000000000000001EB3EAA3AD86242E1D51DC502DD6566BD39E06A668400000000000000C732103F5C5BB1D19EC710D3D7FAD199AF10CF8BC1D11348E5B3765C0B0B9C0BEC328798114A6C3D314FB5418627AB22D9DDF6C18AED5F6CA89";
       let output = serialize_tx(input.to_string(), true, None);
       assert_eq!(output.unwrap(), expected);
+      assert_round_trip(input);
     }
 
     #[test]
@@ -124,6 +1196,7 @@ mod tests {
         let expected= "120007220008000024001ABED82A2380BF2C2019001ABED764D55920AC9391400000000000000000000000000055534400000000000A20B3C85F482532A9578DBB3950B85CA06594D165400000037E11D60068400000000000000A732103EE83BB432547885C219634A1BC407A9DB0474145D69737D09CCDC63E1DEE7FE38114DD76483FACDEE26E60D8A586BB58D09F27045C46";
         let output = serialize_tx(input.to_string(), true, None);
        assert_eq!(output.unwrap(), expected);
+       assert_round_trip(input);
     }
 
     #[test]
@@ -144,6 +1217,7 @@ mod tests {
       let expected= "1200002280000000230000000024000D6BA16140000001640C3C906840000000000003E873210255EECA852E7C26C0219F0792D1229F1147366D4C936FF3ED83AC32354F6F8EF38114E23E1F811DC4A4AD525F73D6B17F07C9FA127B388314FF4D447732C13CB9BEC7A4653B08304AAB63F519";
       let output = serialize_tx(input.to_string(), true, None);
       assert_eq!(output.unwrap(), expected);
+      assert_round_trip(input);
     }
 
     #[test]
@@ -164,6 +1238,7 @@ mod tests {
       let expected= "1200002280000000230000000024000C8A5761400000001DCD61186840000000000003E873210255EECA852E7C26C0219F0792D1229F1147366D4C936FF3ED83AC32354F6F8EF38114E23E1F811DC4A4AD525F73D6B17F07C9FA127B3883147839399F25EC87AFB3C7DAB8243DDD0C46C421DE";
       let output = serialize_tx(input.to_string(), true, None);
       assert_eq!(output.unwrap(), expected);
+      assert_round_trip(input);
     }
 
     #[test]
@@ -229,6 +1304,21 @@ mod tests {
       let expected= "1200002200000000240000034A201B009717BE61400000000098968068400000000000000C69D4564B964A845AC0000000000000000000000000555344000000000069D33B18D53385F8A3185516C2EDA5DEDB8AC5C673210379F17CFA0FFD7518181594BE69FE9A10471D6DE1F4055C6D2746AFD6CF89889E811469D33B18D53385F8A3185516C2EDA5DEDB8AC5C6831469D33B18D53385F8A3185516C2EDA5DEDB8AC5C6F9EA7C06636C69656E747D077274312E312E31E1F1011201F3B1997562FD742B54D4EBDEA1D6AEA3D4906B8F100000000000000000000000000000000000000000FF014B4E9C06F24296074F7BC48F92A97916C6DC5EA901DD39C650A96EDA48334E70CC4A85B8B2E8502CD310000000000000000000000000000000000000000000";
       let output = serialize_tx(input.to_string(), true, None);
       assert_eq!(output.unwrap(), expected);
+
+      // the nested `Memo` objects and path steps decode back, without the `type` and `type_hex` of the steps which are
+      // implied by their keys, and the non-signing `TxnSignature` and `hash`
+      let mut tx: Value = from_str(input).unwrap();
+      let tx_map = tx.as_object_mut().unwrap();
+      tx_map.remove("TxnSignature");
+      tx_map.remove("hash");
+      for step in tx_map["Paths"].as_array_mut().unwrap().iter_mut().flat_map(|path| path.as_array_mut().unwrap()) {
+        let step = step.as_object_mut().unwrap();
+        step.remove("type");
+        step.remove("type_hex");
+      }
+      let decoded = deserialize_tx(expected.to_string(), None).unwrap();
+      assert_eq!(decoded["Memos"][0]["Memo"]["MemoType"], "636C69656E74");
+      assert_eq!(decoded, tx);
     }
 
     #[test]
@@ -237,5 +1327,938 @@ mod tests {
         let expected= "1200152280000000240161618C201B016174F86840000000001E8480732102B87CEB1507849B6473773155827C0B8C15CB311C6876FBD7FAB95F06D3E18E398114656D3E2961EFABDED0C9CDCFB39FC78D01E9A77683148EED191963FEB29D532F04958BFA087A45F742C7";
         let output = serialize_tx(input.to_string(), true, None);
         assert_eq!(output.unwrap(), expected);
+        assert_round_trip(input);
+    }
+
+    #[test]
+    fn test_serialize_tx_with_stats(){
+        let input = OFFER_CREATE;
+        // without `TxnSignature` and `hash`
+        let options = SerializeOptions { for_signing: true, ..Default::default() };
+        let (output, stats) = serialize_tx_with_stats(input.to_string(), &options, None).unwrap();
+        assert_eq!(output, serialize_tx(input.to_string(), true, None).unwrap());
+        assert_eq!(stats, SerializeStats { field_count: 10, byte_length: 148, nesting_depth: 0, skipped_fields: Vec::new() });
+        // with `TxnSignature`
+        let (_, stats) = serialize_tx_with_stats(input.to_string(), &SerializeOptions::default(), None).unwrap();
+        assert_eq!(stats, SerializeStats { field_count: 11, byte_length: 220, nesting_depth: 0, skipped_fields: Vec::new() });
+
+        // `Memos` is an `STArray` of `STObject`
+        let memos = r#"{"TransactionType":"AccountSet","Memos":[{"Memo":{"MemoData":"72656E74"}}]}"#;
+        let (_, stats) = serialize_tx_with_stats(memos.to_string(), &options, None).unwrap();
+        assert_eq!(stats, SerializeStats { field_count: 2, byte_length: 13, nesting_depth: 2, skipped_fields: Vec::new() });
+        assert_eq!(serialize_tx_with_stats(r#"{"Fee":1.5}"#.to_string(), &options, None), None);
+    }
+
+    #[test]
+    fn test_serialize_tx_network_id(){
+        // `NetworkID` is required on networks whose id is 1024 or above, it's ordered after `TransactionType` and before `Flags`
+        let input = r#"{"TransactionType":"Payment","NetworkID":1025,"Flags":2147483648,"Account":"rweYz56rfmQ98cAdRaeTxQS9wVMGnrdsFp","Destination":"rPDXxSZcuVL3ZWoyU82bcde3zwvmShkRyF","Amount":"1000000","Fee":"12","Sequence":842,"SigningPubKey":"0379F17CFA0FFD7518181594BE69FE9A10471D6DE1F4055C6D2746AFD6CF89889E"}"#;
+        let expected = "120000".to_string() + "2100000401" + "2280000000" + "240000034A6140000000000F424068400000000000000C73210379F17CFA0FFD7518181594BE69FE9A10471D6DE1F4055C6D2746AFD6CF89889E811469D33B18D53385F8A3185516C2EDA5DEDB8AC5C68314F3B1997562FD742B54D4EBDEA1D6AEA3D4906B8F";
+        let output = serialize_tx(input.to_string(), true, None);
+        assert_eq!(output.unwrap(), expected);
+        assert_round_trip(input);
+    }
+
+    #[test]
+    fn test_serialize_tx_amm_deposit_lp_token_out(){
+        // The LP token is an issued amount with a hex currency code, issued by the AMM account.
+        // `LPTokenOut` (Amount, nth 25) has a 2 bytes field id and is ordered after `Fee` (Amount, nth 8).
+        let input = r#"{
+          "TransactionType": "AMMDeposit",
+          "Account": "rweYz56rfmQ98cAdRaeTxQS9wVMGnrdsFp",
+          "Fee": "10",
+          "Flags": 65536,
+          "Sequence": 7,
+          "SigningPubKey": "0379F17CFA0FFD7518181594BE69FE9A10471D6DE1F4055C6D2746AFD6CF89889E",
+          "LPTokenOut": {
+            "currency": "039C99CD9AB0B70B32ECDA51EAAE471625608EA2",
+            "issuer": "rPDXxSZcuVL3ZWoyU82bcde3zwvmShkRyF",
+            "value": "100"
+          }
+        }"#;
+        let expected = "1200242200010000240000000768400000000000000A6019D5038D7EA4C68000039C99CD9AB0B70B32ECDA51EAAE471625608EA2F3B1997562FD742B54D4EBDEA1D6AEA3D4906B8F73210379F17CFA0FFD7518181594BE69FE9A10471D6DE1F4055C6D2746AFD6CF89889E811469D33B18D53385F8A3185516C2EDA5DEDB8AC5C6";
+        let output = serialize_tx(input.to_string(), true, None);
+        assert_eq!(output.unwrap(), expected);
+        assert_round_trip(input);
+    }
+
+    #[test]
+    fn test_serialize_tx_amm_deposit_lp_token_out_with_assets(){
+        // `Asset` and `Asset2` (Issue, nth 3 and 4) have the 2 bytes field ids `0318` and `0418`, ordered after `Account`
+        let input = r#"{
+          "TransactionType": "AMMDeposit",
+          "Account": "rweYz56rfmQ98cAdRaeTxQS9wVMGnrdsFp",
+          "Fee": "10",
+          "Flags": 65536,
+          "Sequence": 7,
+          "SigningPubKey": "0379F17CFA0FFD7518181594BE69FE9A10471D6DE1F4055C6D2746AFD6CF89889E",
+          "Asset": {"currency": "XRP"},
+          "Asset2": {"currency": "USD", "issuer": "rPDXxSZcuVL3ZWoyU82bcde3zwvmShkRyF"},
+          "LPTokenOut": {
+            "currency": "039C99CD9AB0B70B32ECDA51EAAE471625608EA2",
+            "issuer": "rPDXxSZcuVL3ZWoyU82bcde3zwvmShkRyF",
+            "value": "100"
+          }
+        }"#;
+        // the field ids and values, assembled from the rippled field codes
+        let expected = concat!(
+          "120024", "2200010000", "2400000007", "68400000000000000A",
+          "6019", "D5038D7EA4C68000", "039C99CD9AB0B70B32ECDA51EAAE471625608EA2", "F3B1997562FD742B54D4EBDEA1D6AEA3D4906B8F",
+          "73210379F17CFA0FFD7518181594BE69FE9A10471D6DE1F4055C6D2746AFD6CF89889E",
+          "811469D33B18D53385F8A3185516C2EDA5DEDB8AC5C6",
+          "0318", "0000000000000000000000000000000000000000",
+          "0418", "0000000000000000000000005553440000000000", "F3B1997562FD742B54D4EBDEA1D6AEA3D4906B8F",
+        );
+        assert_eq!(serialize_tx(input.to_string(), true, None).unwrap(), expected);
+        assert_round_trip(input);
+    }
+
+    #[test]
+    fn test_serialize_tx_full_excludes_hash(){
+        // `hash` is not serialized according to definitions.json, even if not for signing
+        let input = OFFER_CREATE;
+        let expected= "120007220008000024001ABED82A2380BF2C2019001ABED764D55920AC9391400000000000000000000000000055534400000000000A20B3C85F482532A9578DBB3950B85CA06594D165400000037E11D60068400000000000000A732103EE83BB432547885C219634A1BC407A9DB0474145D69737D09CCDC63E1DEE7FE3744630440220143759437C04F7B61F012563AFE90D8DAFC46E86035E1D965A9CED282C97D4CE02204CFD241E86F17E011298FC1A39B63386C74306A5DE047E213B0F29EFA4571C2C8114DD76483FACDEE26E60D8A586BB58D09F27045C46";
+        let output = serialize_tx(input.to_string(), false, None).unwrap();
+        assert_eq!(output, expected);
+        assert_round_trip(input);
+        assert!(!output.contains("73734B611DDA23D3F5F62E20A173B78AB8406AC5015094DA53F53D39B9EDB06C"));
+    }
+
+    #[test]
+    fn test_serialize_tx_tick_size(){
+        // `TickSize` is a `UInt8` of type code 16, so it's ordered after `Account`
+        for (tick_size, expected) in [(0, "00"), (127, "7F"), (255, "FF")] {
+          let input = format!(r#"{{"TransactionType":"AccountSet","Account":"rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys","Fee":"12","Sequence":5,"TickSize":{}}}"#, tick_size);
+          let output = serialize_tx(input.clone(), true, None).unwrap();
+          assert_eq!(output, format!("120003240000000568400000000000000C8114DD76483FACDEE26E60D8A586BB58D09F27045C46001010{}", expected));
+          assert_round_trip(&input);
+        }
+    }
+
+    #[test]
+    fn test_serialize_tx_payment_channel(){
+        let input = r#"{"Account":"rf1BiGeXwwQoi8Z2ueFYTEXSwuJYfV2Jpn","TransactionType":"PaymentChannelCreate","Amount":"10000","Destination":"rsA2LpzuawewSBQXkiju3YQTMzW13pAAdW","SettleDelay":86400,"PublicKey":"32D2471DB72B27E3310F355BB33E339BF26F8392D5A93D3BC0FC3B566612DA0F0A","CancelAfter":533171558,"DestinationTag":23480,"SourceTag":11747,"Fee":"12","Sequence":1,"Flags":0,"SigningPubKey":""}"#;
+        let expected_order = [
+          "TransactionType", "Flags", "SourceTag", "Sequence", "DestinationTag", "CancelAfter", "SettleDelay",
+          "Amount", "Fee", "PublicKey", "SigningPubKey", "Account", "Destination",
+        ];
+        assert_field_order(input, &expected_order);
+        // the field ids and values in the order above
+        let expected = concat!(
+          "12000D", "2200000000", "2300002DE3", "2400000001", "2E00005BB8", "20241FC78D66", "202700015180",
+          "614000000000002710", "68400000000000000C",
+          "712132D2471DB72B27E3310F355BB33E339BF26F8392D5A93D3BC0FC3B566612DA0F0A", "7300",
+          "81144B4E9C06F24296074F7BC48F92A97916C6DC5EA9", "8314204288D2E47F8EF6C99BCC457966320D12409711",
+        );
+        assert_eq!(serialize_tx(input.to_string(), true, None).unwrap(), expected);
+        assert_round_trip(input);
+
+        let input = r#"{"Account":"rf1BiGeXwwQoi8Z2ueFYTEXSwuJYfV2Jpn","TransactionType":"PaymentChannelFund","Channel":"C1AE6DDDEEC05CF2978C0BAD6FE302948E9533691DC749DCDD3B9E5992CA6198","Amount":"200000","Expiration":543171558,"Fee":"12","Sequence":2,"SigningPubKey":""}"#;
+        let expected = "12000E24000000022A206023E65016C1AE6DDDEEC05CF2978C0BAD6FE302948E9533691DC749DCDD3B9E5992CA6198614000000000030D4068400000000000000C730081144B4E9C06F24296074F7BC48F92A97916C6DC5EA9";
+        assert_eq!(serialize_tx(input.to_string(), true, None).unwrap(), expected);
+        assert_round_trip(input);
+    }
+
+    #[test]
+    fn test_deserialize_tx_preserving_unknown(){
+        // an AccountDelete with a `UInt32` field and a `Blob` field of field code 99, which are not in the definitions
+        let blob = concat!(
+          "1200152280000000240161618C201B016174F8", "206300000005", "6840000000001E8480",
+          "732102B87CEB1507849B6473773155827C0B8C15CB311C6876FBD7FAB95F06D3E18E39", "706303ABCDEF",
+          "8114656D3E2961EFABDED0C9CDCFB39FC78D01E9A77683148EED191963FEB29D532F04958BFA087A45F742C7",
+        );
+        assert_eq!(deserialize_tx(blob.to_string(), None), None);
+        let tx = deserialize_tx_preserving_unknown(blob.to_string(), None).unwrap();
+        assert_eq!(tx[UNKNOWN_FIELDS_KEY], json!(["206300000005", "706303ABCDEF"]));
+        assert_eq!(tx["TransactionType"], "AccountDelete");
+
+        let options = SerializeOptions { preserve_unknown: true, ..Default::default() };
+        assert_eq!(serialize_tx_with_options(tx.to_string(), &options, None).unwrap(), blob);
+        // the unknown fields are rejected without `preserve_unknown`
+        let options = SerializeOptions::default();
+        assert_eq!(serialize_tx_with_options(tx.to_string(), &options, None), Err(UnknownField(UNKNOWN_FIELDS_KEY.to_string())));
+
+        let options = SerializeOptions { preserve_unknown: true, ..Default::default() };
+        let mut invalid = tx.clone();
+        // a known field, `Sequence`
+        invalid[UNKNOWN_FIELDS_KEY] = json!(["2400000005"]);
+        assert_eq!(serialize_tx_with_options(invalid.to_string(), &options, None), Err(InvalidFieldValue(UNKNOWN_FIELDS_KEY.to_string())));
+        invalid[UNKNOWN_FIELDS_KEY] = json!(["2063000005"]);
+        assert_eq!(serialize_tx_with_options(invalid.to_string(), &options, None), Err(InvalidFieldValue(UNKNOWN_FIELDS_KEY.to_string())));
+        // a field of type code 13, which is unknown, can't be skipped
+        assert_eq!(deserialize_tx_preserving_unknown("120015D100000000".to_string(), None), None);
+    }
+
+    #[test]
+    fn test_serialize_tx_nftoken_offer(){
+        let input = r#"{"TransactionType":"NFTokenCreateOffer","Account":"rf1BiGeXwwQoi8Z2ueFYTEXSwuJYfV2Jpn","NFTokenID":"000100001E962F495F07A990F4ED55ACCFEEF365DBAA76B6A048C0A200000007","Amount":"1000000","Owner":"rsoLo2S1kiGeCcn6hCUXVrCpGMWLrRrLZz","Destination":"rPDXxSZcuVL3ZWoyU82bcde3zwvmShkRyF","Expiration":595640108,"Flags":0,"Fee":"10","Sequence":3,"SigningPubKey":""}"#;
+        // `NFTokenID` is a Hash256 of type code 5, ordered before the Amount and AccountID fields
+        let expected_order = [
+          "TransactionType", "Flags", "Sequence", "Expiration", "NFTokenID",
+          "Amount", "Fee", "SigningPubKey", "Account", "Owner", "Destination",
+        ];
+        assert_field_order(input, &expected_order);
+        // the field ids and values in the order above
+        let expected = concat!(
+          "12001B", "2200000000", "2400000003", "2A2380BF2C",
+          "5A000100001E962F495F07A990F4ED55ACCFEEF365DBAA76B6A048C0A200000007",
+          "6140000000000F4240", "68400000000000000A", "7300",
+          "81144B4E9C06F24296074F7BC48F92A97916C6DC5EA9", "82141EB3EAA3AD86242E1D51DC502DD6566BD39E06A6",
+          "8314F3B1997562FD742B54D4EBDEA1D6AEA3D4906B8F",
+        );
+        assert_eq!(serialize_tx(input.to_string(), true, None).unwrap(), expected);
+        assert_round_trip(input);
+
+        let input = r#"{"TransactionType":"NFTokenAcceptOffer","Account":"rf1BiGeXwwQoi8Z2ueFYTEXSwuJYfV2Jpn","NFTokenSellOffer":"68CD1F6F906494EA08C9CB5CAFA64DFA90D4E834B7151899B73231DE5A0C3B77","Fee":"10","Sequence":4,"SigningPubKey":""}"#;
+        let expected = "12001D2400000004501D68CD1F6F906494EA08C9CB5CAFA64DFA90D4E834B7151899B73231DE5A0C3B7768400000000000000A730081144B4E9C06F24296074F7BC48F92A97916C6DC5EA9";
+        assert_eq!(serialize_tx(input.to_string(), true, None).unwrap(), expected);
+        assert_round_trip(input);
+    }
+
+    #[test]
+    fn test_serialize_tx_mpt_payment(){
+        let input = r#"{"TransactionType":"Payment","Account":"rf1BiGeXwwQoi8Z2ueFYTEXSwuJYfV2Jpn","Destination":"rPDXxSZcuVL3ZWoyU82bcde3zwvmShkRyF","Amount":{"mpt_issuance_id":"00000004A407AF5856CCF3C42619DAA925813FC955C72983","value":"1000"},"SendMax":{"mpt_issuance_id":"00000004A407AF5856CCF3C42619DAA925813FC955C72983","value":"1100"},"DeliverMin":{"mpt_issuance_id":"00000004A407AF5856CCF3C42619DAA925813FC955C72983","value":"900"},"Fee":"10","Flags":131072,"Sequence":6,"SigningPubKey":""}"#;
+        let expected_order = [
+          "TransactionType", "Flags", "Sequence", "Amount", "Fee", "SendMax", "DeliverMin", "SigningPubKey", "Account", "Destination",
+        ];
+        assert_field_order(input, &expected_order);
+        // each MPT amount is 0x60, the 64 bits value and the 192 bits `mpt_issuance_id`
+        let expected = concat!(
+          "120000", "2200020000", "2400000006",
+          "61", "6000000000000003E8", "00000004A407AF5856CCF3C42619DAA925813FC955C72983",
+          "68400000000000000A",
+          "69", "60000000000000044C", "00000004A407AF5856CCF3C42619DAA925813FC955C72983",
+          "6A", "600000000000000384", "00000004A407AF5856CCF3C42619DAA925813FC955C72983",
+          "7300", "81144B4E9C06F24296074F7BC48F92A97916C6DC5EA9", "8314F3B1997562FD742B54D4EBDEA1D6AEA3D4906B8F",
+        );
+        assert_eq!(serialize_tx(input.to_string(), true, None).unwrap(), expected);
+        assert_round_trip(input);
+        assert_eq!(validate_blob_length(expected, None), Ok(()));
+    }
+
+    #[test]
+    fn test_serialize_tx_amm_deposit(){
+        // `Asset` and `Asset2` are `Issue` of type code 24, ordered after `Account`
+        let input = r#"{"TransactionType":"AMMDeposit","Account":"rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys","Asset":{"currency":"XRP"},"Asset2":{"currency":"ETH","issuer":"rPDXxSZcuVL3ZWoyU82bcde3zwvmShkRyF"},"Amount":"1000000","Fee":"10","Flags":524288,"Sequence":5,"SigningPubKey":""}"#;
+        let expected = concat!(
+          "120024", "2200080000", "2400000005", "6140000000000F4240", "68400000000000000A", "7300",
+          "8114DD76483FACDEE26E60D8A586BB58D09F27045C46",
+          "0318", "0000000000000000000000000000000000000000",
+          "0418", "0000000000000000000000004554480000000000", "F3B1997562FD742B54D4EBDEA1D6AEA3D4906B8F",
+        );
+        assert_eq!(serialize_tx(input.to_string(), true, None).unwrap(), expected);
+        assert_round_trip(input);
+    }
+
+    #[test]
+    fn test_serialize_tx_signer_list_delete(){
+        // a `SignerQuorum` of 0 without `SignerEntries` deletes the signer list
+        let input = r#"{"TransactionType":"SignerListSet","Account":"rf1BiGeXwwQoi8Z2ueFYTEXSwuJYfV2Jpn","Fee":"12","Flags":0,"Sequence":5,"SignerQuorum":0,"SigningPubKey":""}"#;
+        let expected = "12000C2200000000240000000520230000000068400000000000000C730081144B4E9C06F24296074F7BC48F92A97916C6DC5EA9";
+        assert_eq!(serialize_tx(input.to_string(), true, None).unwrap(), expected);
+        assert_round_trip(input);
+
+        let options = SerializeOptions { validate: true, ..Default::default() };
+        assert_eq!(serialize_tx_with_options(input.to_string(), &options, None).unwrap(), expected);
+        let input = r#"{"TransactionType":"SignerListSet","SignerQuorum":0,"SignerEntries":[{"SignerEntry":{"Account":"rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B","SignerWeight":1}}]}"#;
+        assert_eq!(serialize_tx_with_options(input.to_string(), &options, None), Err(ConflictingFields("SignerQuorum".to_string(), "SignerEntries".to_string())));
+    }
+
+    #[test]
+    fn test_parsed_transaction_try_from(){
+        // the blob of `test_serialize_tx5`
+        let blob = ACCOUNT_DELETE_BLOB;
+        let tx: ParsedTransaction = blob.try_into().unwrap();
+        assert_eq!(tx.get("Destination").unwrap(), "rNp5zaiaR3maZ8zALz5CWnqRYXWkeGhteS");
+        assert_eq!(tx.get("Comment"), None);
+        assert_eq!(Some(tx.data), deserialize_tx(blob.to_string(), None));
+        assert!(matches!(ParsedTransaction::try_from("12001"), Err(DecodeError(_))));
+        assert_eq!(ParsedTransaction::try_from("1200"), Err(Truncated));
+    }
+
+    #[test]
+    fn test_deserialize_tx_account_delete(){
+        // the blob of `test_serialize_tx5`
+        let blob = ACCOUNT_DELETE_BLOB;
+        let decoded = deserialize_tx(blob.to_string(), None).unwrap();
+        assert_eq!(decoded["Destination"], "rNp5zaiaR3maZ8zALz5CWnqRYXWkeGhteS");
+        assert_eq!(decoded["Fee"], "2000000");
+        assert_eq!(decoded["LastLedgerSequence"], 23164152);
+        assert_eq!(serialize_tx_value(&decoded, true, None).unwrap(), blob);
+    }
+
+    #[test]
+    fn test_serialize_tx_ticket_create(){
+        let input = r#"{"TransactionType":"TicketCreate","Account":"rMdG3ju8pgyVh29ELPWaDuA74CpWW6Fxns","Fee":"12","Flags":0,"Sequence":879522,"TicketCount":10,"SigningPubKey":"0255EECA852E7C26C0219F0792D1229F1147366D4C936FF3ED83AC32354F6F8EF3"}"#;
+        // TransactionType, Flags, Sequence, TicketCount, Fee, SigningPubKey, Account
+        let expected= "12000A220000000024000D6BA220280000000A68400000000000000C73210255EECA852E7C26C0219F0792D1229F1147366D4C936FF3ED83AC32354F6F8EF38114E23E1F811DC4A4AD525F73D6B17F07C9FA127B38";
+        let output = serialize_tx(input.to_string(), true, None);
+        assert_eq!(output.unwrap(), expected);
+        assert_round_trip(input);
+    }
+
+    #[test]
+    fn test_serialize_tx_ticket_sequence(){
+        // A transaction using a ticket sets `Sequence` to 0.
+        let input = r#"{"TransactionType":"Payment","Account":"rMdG3ju8pgyVh29ELPWaDuA74CpWW6Fxns","Amount":"1000000","Destination":"rQGu1Zh1rBNt5eCDfuvR1zvV9MT8CPgwLk","Fee":"12","Flags":2147483648,"Sequence":0,"TicketSequence":879523,"SigningPubKey":"0255EECA852E7C26C0219F0792D1229F1147366D4C936FF3ED83AC32354F6F8EF3"}"#;
+        // TransactionType, Flags, Sequence, TicketSequence, Amount, Fee, SigningPubKey, Account, Destination
+        let expected= "120000228000000024000000002029000D6BA36140000000000F424068400000000000000C73210255EECA852E7C26C0219F0792D1229F1147366D4C936FF3ED83AC32354F6F8EF38114E23E1F811DC4A4AD525F73D6B17F07C9FA127B388314FF4D447732C13CB9BEC7A4653B08304AAB63F519";
+        let output = serialize_tx(input.to_string(), true, None).unwrap();
+        assert!(output.contains("2400000000"));
+        assert_eq!(output, expected);
+        assert_round_trip(input);
+    }
+
+    #[test]
+    fn test_serialize_tx_account_set(){
+        let input = r#"{
+          "TransactionType": "AccountSet",
+          "Account": "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys",
+          "Fee": "12",
+          "Flags": 0,
+          "Sequence": 1752793,
+          "SetFlag": 8,
+          "ClearFlag": 6,
+          "TransferRate": 1002000000,
+          "Domain": "6578616D706C652E636F6D",
+          "MessageKey": "03AB40A0490F9B7ED8DF29D246BF2D6269820A0EE7742ACDD457BEA7C7D0931EDB",
+          "SigningPubKey": "03EE83BB432547885C219634A1BC407A9DB0474145D69737D09CCDC63E1DEE7FE3"
+        }"#;
+        let expected_order = ["TransactionType", "Flags", "Sequence", "TransferRate", "SetFlag", "ClearFlag", "Fee", "MessageKey", "SigningPubKey", "Domain", "Account"];
+        assert_field_order(input, &expected_order);
+
+        let expected= "120003220000000024001ABED92B3BB94E8020210000000820220000000668400000000000000C722103AB40A0490F9B7ED8DF29D246BF2D6269820A0EE7742ACDD457BEA7C7D0931EDB732103EE83BB432547885C219634A1BC407A9DB0474145D69737D09CCDC63E1DEE7FE3770B6578616D706C652E636F6D8114DD76483FACDEE26E60D8A586BB58D09F27045C46";
+        let output = serialize_tx(input.to_string(), true, None);
+        assert_eq!(output.unwrap(), expected);
+        assert_round_trip(input);
+    }
+
+    #[test]
+    fn test_serialize_tx_offer_create_issued_both_sides(){
+        let input = r#"{
+          "TransactionType": "OfferCreate",
+          "Account": "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys",
+          "Fee": "12",
+          "Flags": 0,
+          "Sequence": 1752794,
+          "TakerGets": {
+            "currency": "USD",
+            "issuer": "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B",
+            "value": "100"
+          },
+          "TakerPays": {
+            "currency": "534F4C4F00000000000000000000000000000000",
+            "issuer": "rsoLo2S1kiGeCcn6hCUXVrCpGMWLrRrLZz",
+            "value": "2500.5"
+          },
+          "SigningPubKey": "03EE83BB432547885C219634A1BC407A9DB0474145D69737D09CCDC63E1DEE7FE3"
+        }"#;
+        // TakerPays (Amount, nth 4) is serialized before TakerGets (Amount, nth 5), each as 8 bytes value + 20 bytes currency + 20 bytes issuer.
+        let taker_pays = "64D548E2310642C800534F4C4F000000000000000000000000000000001EB3EAA3AD86242E1D51DC502DD6566BD39E06A6";
+        let taker_gets = "65D5038D7EA4C6800000000000000000000000000055534400000000000A20B3C85F482532A9578DBB3950B85CA06594D1";
+        let expected= format!("120007220000000024001ABEDA{}{}68400000000000000C732103EE83BB432547885C219634A1BC407A9DB0474145D69737D09CCDC63E1DEE7FE38114DD76483FACDEE26E60D8A586BB58D09F27045C46", taker_pays, taker_gets);
+        let output = serialize_tx(input.to_string(), true, None);
+        assert_eq!(output.unwrap(), expected);
+        assert_round_trip(input);
+    }
+
+    #[test]
+    fn test_serialize_tx_vector256(){
+        // `TokenIDs` (Vector256, nth 4) is VL-encoded, the 32 bytes hashes are concatenated after the length
+        let input = r#"{"TransactionType":"NFTokenCancelOffer","Fee":"12","TokenIDs":["73734B611DDA23D3F5F62E20A173B78AB8406AC5015094DA53F53D39B9EDB06C","E922D7E4CBEBAF0D670D20220F1735A105D8C1ECCB42C0ED10AC6FF975DC06C0"]}"#;
+        let expected = concat!(
+          "12001C", "68400000000000000C", "041340",
+          "73734B611DDA23D3F5F62E20A173B78AB8406AC5015094DA53F53D39B9EDB06C", "E922D7E4CBEBAF0D670D20220F1735A105D8C1ECCB42C0ED10AC6FF975DC06C0",
+        );
+        assert_eq!(serialize_tx(input.to_string(), true, None).unwrap(), expected);
+        assert_round_trip(input);
+        assert_eq!(serialize_tx(r#"{"TokenIDs":[]}"#.to_string(), true, None).unwrap(), "041300");
+        assert_eq!(serialize_tx(r#"{"TokenIDs":["73734B61"]}"#.to_string(), true, None), None);
+        assert_eq!(serialize_tx(r#"{"TokenIDs":"73734B611DDA23D3F5F62E20A173B78AB8406AC5015094DA53F53D39B9EDB06C"}"#.to_string(), true, None), None);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_serialize_tx_vectors(){
+        let definition_fields = DefinitionFields::new();
+        let vectors: Value = from_str(include_str!("fixtures/serialize_vectors.json")).unwrap();
+        let mut checked = 0;
+        for vector in vectors["whole_objects"].as_array().unwrap() {
+          let tx = &vector["tx_json"];
+          let blob = vector["blob"].as_str().unwrap();
+          let decoded = deserialize_tx(blob.to_string(), Some(&definition_fields));
+          assert!(decoded.is_some(), "{}", blob);
+          let output = serialize_tx_value(tx, false, Some(&definition_fields));
+          assert_eq!(output.as_deref(), Some(blob), "{}", tx);
+          let output = serialize_tx_value(&decoded.unwrap(), false, Some(&definition_fields));
+          assert_eq!(output.as_deref(), Some(blob), "{}", tx);
+          checked += 1;
+        }
+        // including the `NFTokenCancelOffer` with a `Vector256`
+        assert_eq!(checked, 41);
+    }
+
+    #[test]
+    fn test_deserialize_tx(){
+        // for signing, without `TxnSignature`
+        let input = ACCOUNT_DELETE_BLOB;
+        let expected = json!({
+          "TransactionType": "AccountDelete",
+          "Fee": "2000000",
+          "Flags": 2147483648u32,
+          "Destination": "rNp5zaiaR3maZ8zALz5CWnqRYXWkeGhteS",
+          "Account": "rwEJf6YSKALUaxRhvJ1S81PPmXzWhDW8on",
+          "Sequence": 23159180,
+          "LastLedgerSequence": 23164152,
+          "SigningPubKey": "02B87CEB1507849B6473773155827C0B8C15CB311C6876FBD7FAB95F06D3E18E39"
+        });
+        assert_eq!(deserialize_tx(input.to_string(), None), Some(expected));
+
+        // issued values are rendered in the shortest form
+        let amount = |value: &str| {
+          let tx = json!({"TransactionType": "Payment", "Amount": {"currency": "USD", "issuer": "rf1BiGeXwwQoi8Z2ueFYTEXSwuJYfV2Jpn", "value": value}});
+          let decoded = deserialize_tx(serialize_tx(tx.to_string(), false, None).unwrap(), None).unwrap();
+          decoded["Amount"]["value"].as_str().unwrap().to_string()
+        };
+        assert_eq!(amount("7072.80"), "7072.8");
+        assert_eq!(amount("-12.123"), "-12.123");
+        assert_eq!(amount("1000000"), "1000000");
+        assert_eq!(amount("0.0000005"), "5e-7");
+        assert_eq!(amount("0.000001"), "0.000001");
+        assert_eq!(amount("1.5e25"), "1.5e+25");
+        assert_eq!(amount("0"), "0");
+
+        assert_eq!(deserialize_tx("120000".to_string(), None), Some(json!({"TransactionType": "Payment"})));
+        assert_eq!(deserialize_tx("".to_string(), None), Some(json!({})));
+        // truncated
+        assert_eq!(deserialize_tx("120015228000".to_string(), None), None);
+        // not hex
+        assert_eq!(deserialize_tx("12001X".to_string(), None), None);
+        // an end marker without an `STObject`
+        assert_eq!(deserialize_tx("1200E1".to_string(), None), None);
+        // unknown transaction type
+        assert_eq!(deserialize_tx("12FFFF".to_string(), None), None);
+    }
+
+    #[test]
+    fn test_serialize_tx_check_create(){
+        let input = r#"{
+          "TransactionType": "CheckCreate",
+          "Account": "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys",
+          "Destination": "rGUmkyLbvqGF3hwX4qwGHdrzLdY2Qpskum",
+          "SendMax": "100000000",
+          "Expiration": 570113521,
+          "InvoiceID": "6F1DFD1D0FE8A32E40E1F2C05CF1C15545BAB56B617F9C6C2D63A6B704BEF59B",
+          "DestinationTag": 1,
+          "Fee": "12",
+          "Flags": 0,
+          "Sequence": 7,
+          "SigningPubKey": "03EE83BB432547885C219634A1BC407A9DB0474145D69737D09CCDC63E1DEE7FE3"
+        }"#;
+        let definition_fields = DefinitionFields::new();
+        // ordered by type code, then by field code
+        let expected_order = ["TransactionType", "Flags", "Sequence", "Expiration", "DestinationTag", "InvoiceID", "Fee", "SendMax", "SigningPubKey", "Account", "Destination"];
+        assert_field_order(input, &expected_order);
+        let expected = "120010220000000024000000072A21FB3DF12E0000000150116F1DFD1D0FE8A32E40E1F2C05CF1C15545BAB56B617F9C6C2D63A6B704BEF59B68400000000000000C694000000005F5E100732103EE83BB432547885C219634A1BC407A9DB0474145D69737D09CCDC63E1DEE7FE38114DD76483FACDEE26E60D8A586BB58D09F27045C468314A6C3D314FB5418627AB22D9DDF6C18AED5F6CA89";
+        assert_eq!(serialize_tx(input.to_string(), true, Some(&definition_fields)).unwrap(), expected);
+        assert_round_trip(input);
+    }
+
+    #[test]
+    fn test_serialize_tx_multi_signed(){
+        // `Signers` are given out of order, `SigningPubKey` of the transaction is blank
+        let input = r#"{
+          "TransactionType": "Payment",
+          "Account": "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys",
+          "Destination": "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B",
+          "Amount": "1000000",
+          "Fee": "36",
+          "Sequence": 2,
+          "Flags": 2147483648,
+          "SigningPubKey": "",
+          "Signers": [
+            {
+              "Signer": {
+                "Account": "rQGu1Zh1rBNt5eCDfuvR1zvV9MT8CPgwLk",
+                "TxnSignature": "30440220143759437C04F7B61F012563AFE90D8DAFC46E86035E1D965A9CED282C97D4CE02204CFD241E86F17E011298FC1A39B63386C74306A5DE047E213B0F29EFA4571C2C",
+                "SigningPubKey": "03EE83BB432547885C219634A1BC407A9DB0474145D69737D09CCDC63E1DEE7FE3"
+              }
+            },
+            {
+              "Signer": {
+                "Account": "rGUmkyLbvqGF3hwX4qwGHdrzLdY2Qpskum",
+                "TxnSignature": "30440220143759437C04F7B61F012563AFE90D8DAFC46E86035E1D965A9CED282C97D4CE02204CFD241E86F17E011298FC1A39B63386C74306A5DE047E213B0F29EFA4571C2C",
+                "SigningPubKey": "03F5C5BB1D19EC710D3D7FAD199AF10CF8BC1D11348E5B3765C0B0B9C0BEC32879"
+              }
+            }
+          ]
+        }"#;
+        let expected = "120000228000000024000000026140000000000F424068400000000000002473008114DD76483FACDEE26E60D8A586BB58D09F27045C4683140A20B3C85F482532A9578DBB3950B85CA06594D1F3E010732103F5C5BB1D19EC710D3D7FAD199AF10CF8BC1D11348E5B3765C0B0B9C0BEC32879744630440220143759437C04F7B61F012563AFE90D8DAFC46E86035E1D965A9CED282C97D4CE02204CFD241E86F17E011298FC1A39B63386C74306A5DE047E213B0F29EFA4571C2C8114A6C3D314FB5418627AB22D9DDF6C18AED5F6CA89E1E010732103EE83BB432547885C219634A1BC407A9DB0474145D69737D09CCDC63E1DEE7FE3744630440220143759437C04F7B61F012563AFE90D8DAFC46E86035E1D965A9CED282C97D4CE02204CFD241E86F17E011298FC1A39B63386C74306A5DE047E213B0F29EFA4571C2C8114FF4D447732C13CB9BEC7A4653B08304AAB63F519E1F1";
+        assert_eq!(serialize_tx(input.to_string(), false, None).unwrap(), expected);
+        // `Signers` is not a signing field, the blank `SigningPubKey` is kept
+        let expected_for_signing = "120000228000000024000000026140000000000F424068400000000000002473008114DD76483FACDEE26E60D8A586BB58D09F27045C4683140A20B3C85F482532A9578DBB3950B85CA06594D1";
+        assert_eq!(serialize_tx(input.to_string(), true, None).unwrap(), expected_for_signing);
+    }
+
+    #[test]
+    fn test_signing_blob(){
+        // the transaction of `test_serialize_tx_1`, `TxnSignature` is not signed
+        let input = r#"{"Account":"rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys","Expiration":595640108,"Fee":"10","Flags":524288,"OfferSequence":1752791,"Sequence":1752792,"SigningPubKey":"03EE83BB432547885C219634A1BC407A9DB0474145D69737D09CCDC63E1DEE7FE3","TakerGets":"15000000000","TakerPays":{"currency":"USD","issuer":"rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B","value":"7072.8"},"TransactionType":"OfferCreate","TxnSignature":"30440220143759437C04F7B61F012563AFE90D8DAFC46E86035E1D965A9CED282C97D4CE02204CFD241E86F17E011298FC1A39B63386C74306A5DE047E213B0F29EFA4571C2C"}"#;
+        let blob = signing_blob(input, None).unwrap();
+        assert_eq!(hex::encode_upper(&blob), serialize_tx(input.to_string(), true, None).unwrap());
+        assert_ne!(hex::encode_upper(&blob), serialize_tx(input.to_string(), false, None).unwrap());
+        // a transaction without `TxnSignature` is the same for signing, the blob of `test_serialize_tx`
+        let blob = signing_blob(TRUST_SET, None).unwrap();
+        assert_eq!(hex::encode_upper(&blob), "12001422800200002404C49431201B04CAF59363D7038D7EA4C68000534F4C4F000000000000000000000000000000001EB3EAA3AD86242E1D51DC502DD6566BD39E06A668400000000000000C732103F5C5BB1D19EC710D3D7FAD199AF10CF8BC1D11348E5B3765C0B0B9C0BEC328798114A6C3D314FB5418627AB22D9DDF6C18AED5F6CA89");
+        assert_eq!(signing_blob("{", None), None);
+    }
+
+    #[test]
+    fn test_serialize_for_multisign(){
+        let input = r#"{
+          "TransactionType": "Payment",
+          "Account": "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys",
+          "Destination": "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B",
+          "Amount": "1000000",
+          "Fee": "36",
+          "Sequence": 2,
+          "Flags": 2147483648,
+          "SigningPubKey": "03EE83BB432547885C219634A1BC407A9DB0474145D69737D09CCDC63E1DEE7FE3",
+          "TxnSignature": "30440220143759437C04F7B61F012563AFE90D8DAFC46E86035E1D965A9CED282C97D4CE02204CFD241E86F17E011298FC1A39B63386C74306A5DE047E213B0F29EFA4571C2C",
+          "Signers": [
+            {
+              "Signer": {
+                "Account": "rGUmkyLbvqGF3hwX4qwGHdrzLdY2Qpskum",
+                "TxnSignature": "30440220143759437C04F7B61F012563AFE90D8DAFC46E86035E1D965A9CED282C97D4CE02204CFD241E86F17E011298FC1A39B63386C74306A5DE047E213B0F29EFA4571C2C",
+                "SigningPubKey": "03F5C5BB1D19EC710D3D7FAD199AF10CF8BC1D11348E5B3765C0B0B9C0BEC32879"
+              }
+            }
+          ]
+        }"#;
+        // `SigningPubKey` is `73` followed by the `00` length prefix
+        let expected = "120000228000000024000000026140000000000F424068400000000000002473008114DD76483FACDEE26E60D8A586BB58D09F27045C4683140A20B3C85F482532A9578DBB3950B85CA06594D1";
+        assert_eq!(serialize_for_multisign(input, None).unwrap(), expected);
+        // without a `SigningPubKey`
+        let mut tx: Value = from_str(input).unwrap();
+        tx.as_object_mut().unwrap().remove("SigningPubKey");
+        assert_eq!(serialize_for_multisign(&tx.to_string(), None).unwrap(), expected);
+        assert_eq!(serialize_for_multisign("[]", None), None);
+    }
+
+    #[test]
+    fn test_deserialize_tx_uint64(){
+        // rippled renders `UInt64` as 16 characters uppercase hex, not a number
+        let decoded = deserialize_tx("34000000000000000A".to_string(), None).unwrap();
+        assert_eq!(decoded, json!({"OwnerNode": "000000000000000A"}));
+        assert_eq!(serialize_tx_value(&decoded, false, None).unwrap(), "34000000000000000A");
+    }
+
+    #[test]
+    fn test_deserialize_tx_empty_signing_pub_key(){
+        // a multi-signed transaction has an empty `SigningPubKey`, serialized as `7300`, which is present as "" rather than absent
+        let blob = "12001D2400000004501D68CD1F6F906494EA08C9CB5CAFA64DFA90D4E834B7151899B73231DE5A0C3B7768400000000000000A730081144B4E9C06F24296074F7BC48F92A97916C6DC5EA9";
+        let decoded = deserialize_tx(blob.to_string(), None).unwrap();
+        assert_eq!(decoded["SigningPubKey"], json!(""));
+        assert_eq!(serialize_tx_value(&decoded, false, None).unwrap(), blob);
+    }
+
+    #[test]
+    fn test_deserialize_tx_memos(){
+        let input = r#"{
+          "TransactionType": "AccountSet",
+          "Account": "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys",
+          "Fee": "12",
+          "Sequence": 5,
+          "Flags": 0,
+          "Memos": [
+            {
+              "Memo": {
+                "MemoType": "687474703A2F2F6578616D706C652E636F6D2F6D656D6F2F67656E65726963",
+                "MemoData": "72656E74",
+                "MemoFormat": "746578742F706C61696E"
+              }
+            },
+            {
+              "Memo": {
+                "MemoData": "72656E74"
+              }
+            },
+            {
+              "Memo": {
+                "MemoType": "636C69656E74"
+              }
+            }
+          ],
+          "SigningPubKey": "03EE83BB432547885C219634A1BC407A9DB0474145D69737D09CCDC63E1DEE7FE3"
+        }"#;
+        let expected = "1200032200000000240000000568400000000000000C732103EE83BB432547885C219634A1BC407A9DB0474145D69737D09CCDC63E1DEE7FE38114DD76483FACDEE26E60D8A586BB58D09F27045C46F9EA7C1F687474703A2F2F6578616D706C652E636F6D2F6D656D6F2F67656E657269637D0472656E747E0A746578742F706C61696EE1EA7D0472656E74E1EA7C06636C69656E74E1F1";
+        let output = serialize_tx(input.to_string(), false, None).unwrap();
+        assert_eq!(output, expected);
+        let decoded = deserialize_tx(output, None).unwrap();
+        assert_eq!(decoded["Memos"][0], json!({
+          "Memo": {
+            "MemoType": "687474703A2F2F6578616D706C652E636F6D2F6D656D6F2F67656E65726963",
+            "MemoData": "72656E74",
+            "MemoFormat": "746578742F706C61696E"
+          }
+        }));
+        assert_round_trip(input);
+    }
+
+    #[test]
+    fn test_classify_blob(){
+        let transaction = ACCOUNT_DELETE_BLOB;
+        assert_eq!(classify_blob(transaction, None), BlobKind::Transaction);
+
+        // an `AccountRoot`
+        let input = r#"{"LedgerEntryType":97,"Flags":0,"Sequence":1,"Balance":"1000000","Account":"rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys"}"#;
+        let ledger_entry = serialize_tx(input.to_string(), false, None).unwrap();
+        assert_eq!(ledger_entry, "110061220000000024000000016240000000000F42408114DD76483FACDEE26E60D8A586BB58D09F27045C46");
+        assert_eq!(classify_blob(&ledger_entry, None), BlobKind::LedgerEntry);
+        let decoded = deserialize_tx(ledger_entry.clone(), None).unwrap();
+        assert_eq!(decoded["LedgerEntryType"], "AccountRoot");
+        // the decoded entry with the named `LedgerEntryType` serializes back
+        assert_eq!(serialize_tx(decoded.to_string(), false, None), Some(ledger_entry));
+
+        // only the fields before the marker are parsed
+        assert_eq!(classify_blob(&transaction[..8], None), BlobKind::Transaction);
+        assert_eq!(classify_blob("2200000000", None), BlobKind::Unknown);
+        assert_eq!(classify_blob("", None), BlobKind::Unknown);
+        assert_eq!(classify_blob("22000000", None), BlobKind::Unknown);
+        assert_eq!(classify_blob("XX", None), BlobKind::Unknown);
+    }
+
+    #[test]
+    fn test_serialize_tx_with_options(){
+        let input = ACCOUNT_DELETE;
+        let expected = ACCOUNT_DELETE_BLOB;
+        let options = SerializeOptions { for_signing: true, validate: true, ..Default::default() };
+        assert_eq!(serialize_tx_with_options(input.to_string(), &options, None).unwrap(), expected);
+
+        let input = r#"{"TransactionType":"AccountDelete","Fee":"2000000","Comment":"rent"}"#;
+        assert_eq!(serialize_tx_with_options(input.to_string(), &options, None), Err(UnknownField("Comment".to_string())));
+    }
+
+    #[test]
+    fn test_serialize_tx_with_options_allowed_fields(){
+        let input = ACCOUNT_DELETE;
+        let expected = ACCOUNT_DELETE_BLOB;
+        let allowed_fields: BTreeSet<String> = ["TransactionType", "Fee", "Flags", "Destination", "Account", "Sequence", "LastLedgerSequence", "SigningPubKey"]
+          .iter().map(|field_name| field_name.to_string()).collect();
+        let options = SerializeOptions { for_signing: true, allowed_fields: Some(allowed_fields.clone()), ..Default::default() };
+        assert_eq!(serialize_tx_with_options(input.to_string(), &options, None).unwrap(), expected);
+
+        let mut tx: Value = from_str(input).unwrap();
+        tx["DestinationTag"] = json!(7);
+        assert_eq!(serialize_tx_with_options(tx.to_string(), &options, None), Err(DisallowedField("DestinationTag".to_string())));
+
+        // nested fields are checked as well
+        let mut tx: Value = from_str(input).unwrap();
+        tx["Memos"] = json!([{"Memo": {"MemoData": "72656E74"}}]);
+        let mut with_memos = allowed_fields;
+        with_memos.insert("Memos".to_string());
+        with_memos.insert("Memo".to_string());
+        let options = SerializeOptions { for_signing: true, allowed_fields: Some(with_memos.clone()), ..Default::default() };
+        assert_eq!(serialize_tx_with_options(tx.to_string(), &options, None), Err(DisallowedField("MemoData".to_string())));
+        with_memos.insert("MemoData".to_string());
+        let options = SerializeOptions { for_signing: true, allowed_fields: Some(with_memos), ..Default::default() };
+        assert!(serialize_tx_with_options(tx.to_string(), &options, None).is_ok());
+    }
+
+    #[test]
+    fn test_serialize_tx_bytes(){
+        let input = ACCOUNT_DELETE;
+        for for_signing in [true, false] {
+          let options = SerializeOptions { for_signing, ..Default::default() };
+          let output = serialize_tx_bytes(input.to_string(), &options, None).unwrap();
+          let expected = hex::decode(serialize_tx(input.to_string(), for_signing, None).unwrap()).unwrap();
+          assert_eq!(output, expected);
+        }
+        let options = SerializeOptions::default();
+        assert_eq!(serialize_tx_bytes(r#"{"Comment":"rent"}"#.to_string(), &options, None), None);
+    }
+
+    #[test]
+    fn test_serialize_tx_with_options_path_set_limits(){
+        let payment = |paths: usize| {
+          let path = json!([{"account": "rPDXxSZcuVL3ZWoyU82bcde3zwvmShkRyF"}, {"currency": "XRP"}]);
+          json!({
+            "TransactionType": "Payment",
+            "Account": "rweYz56rfmQ98cAdRaeTxQS9wVMGnrdsFp",
+            "Destination": "rweYz56rfmQ98cAdRaeTxQS9wVMGnrdsFp",
+            "Amount": "10000000",
+            "Fee": "12",
+            "Sequence": 842,
+            "Paths": vec![path; paths],
+          }).to_string()
+        };
+        let validate = SerializeOptions { for_signing: true, validate: true, ..Default::default() };
+        assert!(serialize_tx_with_options(payment(6), &validate, None).is_ok());
+        assert_eq!(serialize_tx_with_options(payment(7), &validate, None), Err(PathSetTooLarge { paths: 7, steps: 2 }));
+
+        let no_validate = SerializeOptions { for_signing: true, validate: false, ..Default::default() };
+        assert!(serialize_tx_with_options(payment(7), &no_validate, None).is_ok());
+    }
+
+    #[test]
+    fn test_serialize_tx_collect_errors(){
+        let input = ACCOUNT_DELETE;
+        let expected = ACCOUNT_DELETE_BLOB;
+        assert_eq!(serialize_tx_collect_errors(input.to_string(), true, None).unwrap(), expected);
+
+        let input = r#"{"TransactionType":"AccountDelete","Fee":"2000000","Flags":2147483648,"Destination":"rNp5zaiaR3maZ8zALz5CWnqRYXWkeGhteX","Account":"rwEJf6YSKALUaxRhvJ1S81PPmXzWhDW8on","Sequence":23159180,"LastLedgerSequence":23164152,"SigningPubKey":"02B87CEB1507849B6473773155827C0B8C15CB311C6876FBD7FAB95F06D3E18E39","Amount":"12.5"}"#;
+        let errors = serialize_tx_collect_errors(input.to_string(), true, None).unwrap_err();
+        assert_eq!(errors, vec![InvalidAmount("Amount".to_string()), InvalidFieldValue("Destination".to_string())]);
+
+        let input = r#"{"TransactionType":"AccountDelete","Fee":"2000000","Comment":"rent"}"#;
+        let errors = serialize_tx_collect_errors(input.to_string(), true, None).unwrap_err();
+        assert_eq!(errors, vec![UnknownField("Comment".to_string())]);
+    }
+
+    #[test]
+    fn test_serialize_tx_skip_unknown(){
+        let input = ACCOUNT_DELETE;
+        let mut with_unknown: Value = from_str(input).unwrap();
+        with_unknown["LastLedgerSeq"] = json!(23164152);
+        with_unknown["internal_id"] = json!("order-42");
+        let expected = serialize_tx(input.to_string(), false, None).unwrap();
+
+        // strict by default
+        let options = SerializeOptions::default();
+        assert_eq!(serialize_tx_with_options(with_unknown.to_string(), &options, None), Err(UnknownField("LastLedgerSeq".to_string())));
+
+        let options = SerializeOptions { skip_unknown: true, validate: true, ..Default::default() };
+        assert_eq!(serialize_tx_with_options(with_unknown.to_string(), &options, None), Ok(expected.clone()));
+        let (output, stats) = serialize_tx_with_stats(with_unknown.to_string(), &options, None).unwrap();
+        assert_eq!(output, expected);
+        assert_eq!(stats.skipped_fields, vec!["LastLedgerSeq".to_string(), "internal_id".to_string()]);
+        let (_, stats) = serialize_tx_with_stats(input.to_string(), &options, None).unwrap();
+        assert!(stats.skipped_fields.is_empty());
+        // a known field with an invalid value still fails
+        with_unknown["Sequence"] = json!("1");
+        assert_eq!(serialize_tx_with_options(with_unknown.to_string(), &options, None), Err(InvalidFieldValue("Sequence".to_string())));
+    }
+
+    #[test]
+    fn test_serialize_tx_empty_regular_key(){
+        // a `SetRegularKey` removing the regular key
+        let input = r#"{"TransactionType":"SetRegularKey","Account":"rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys","Fee":"12","Sequence":5,"RegularKey":""}"#;
+        let serialized = serialize_tx(input.to_string(), false, None).unwrap();
+        assert_eq!(serialized, "120005240000000568400000000000000C8114DD76483FACDEE26E60D8A586BB58D09F27045C468800");
+        let decoded = deserialize_tx(serialized, None).unwrap();
+        assert_eq!(decoded, from_str::<Value>(input).unwrap());
+
+        // only the fields of `EMPTY_ACCOUNT_FIELDS` can be empty
+        let input = r#"{"TransactionType":"Payment","Account":"rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys","Fee":"12","Sequence":5,"Destination":""}"#;
+        assert_eq!(serialize_tx(input.to_string(), false, None), None);
+        assert_eq!(deserialize_tx("120000240000000568400000000000000C8300".to_string(), None), None);
+    }
+
+    #[test]
+    fn test_serialize_tx_hex_format(){
+        let input = r#"{"TransactionType":"AccountDelete","Fee":"2000000","Flags":2147483648,"Destination":"rNp5zaiaR3maZ8zALz5CWnqRYXWkeGhteS","Account":"rwEJf6YSKALUaxRhvJ1S81PPmXzWhDW8on","Sequence":23159180}"#;
+        let expected = serialize_tx(input.to_string(), false, None).unwrap();
+        let options = SerializeOptions::default();
+        assert_eq!(serialize_tx_with_options(input.to_string(), &options, None), Ok(expected.clone()));
+
+        let options = SerializeOptions { hex_case: HexCase::Lower, ..Default::default() };
+        assert_eq!(serialize_tx_with_options(input.to_string(), &options, None), Ok(expected.to_lowercase()));
+        let options = SerializeOptions { hex_prefix: true, ..Default::default() };
+        let (output, stats) = serialize_tx_with_stats(input.to_string(), &options, None).unwrap();
+        assert_eq!(output, format!("0x{}", expected));
+        assert_eq!(stats.byte_length, expected.len() / 2);
+
+        assert_eq!(format_blob(&[], HexCase::Lower, true), "0x");
+        assert_eq!(format_blob(&[0xab, 0x01], HexCase::Lower, false), "ab01");
+    }
+
+    #[test]
+    fn test_serialize_tx_named_flags(){
+        let input = r#"{"TransactionType":"Payment","Flags":131072,"Account":"rweYz56rfmQ98cAdRaeTxQS9wVMGnrdsFp","Destination":"rPDXxSZcuVL3ZWoyU82bcde3zwvmShkRyF","Amount":"1000000","Fee":"12","Sequence":842}"#;
+        let expected = serialize_tx(input.to_string(), false, None).unwrap();
+        let mut named: Value = from_str(input).unwrap();
+        named["Flags"] = json!(["tfPartialPayment"]);
+        assert_eq!(serialize_tx(named.to_string(), false, None), Some(expected.clone()));
+
+        assert_eq!(serialize_tx_value(&named, false, None), Some(expected.clone()));
+        assert_eq!(serialize_tx_collect_errors(named.to_string(), false, None), Ok(expected.clone()));
+        assert!(serialize_for_multisign(&named.to_string(), None).unwrap().starts_with("12000022000200002400000"));
+        let field_order = ["TransactionType".to_string(), "Flags".to_string()];
+        assert_eq!(serialize_tx_in_order(named.to_string(), &field_order, &SerializeOptions::default(), None), Ok("1200002200020000".to_string()));
+
+        named["Flags"] = json!(["tfPartialPayment", "tfSell"]);
+        let options = SerializeOptions::default();
+        assert_eq!(serialize_tx_with_options(named.to_string(), &options, None), Err(UnknownFlag("tfSell".to_string())));
+        assert_eq!(serialize_tx_collect_errors(named.to_string(), false, None), Err(vec![UnknownFlag("tfSell".to_string())]));
+        assert_eq!(serialize_tx_value(&named, false, None), None);
+    }
+
+    #[test]
+    fn test_serialize_tx_check_required(){
+        let input = r#"{"TransactionType":"Payment","Account":"rwEJf6YSKALUaxRhvJ1S81PPmXzWhDW8on","Fee":"12","Sequence":1,"Amount":"1000"}"#;
+        let options = SerializeOptions { check_required: true, ..Default::default() };
+        let missing = MissingField { tx_type: "Payment".to_string(), field: "Destination".to_string() };
+        assert_eq!(serialize_tx_with_options(input.to_string(), &options, None), Err(missing));
+        assert!(serialize_tx_with_options(input.to_string(), &SerializeOptions::default(), None).is_ok());
+    }
+
+    #[test]
+    fn test_serialize_tx_in_order(){
+        let input = r#"{"TransactionType":"AccountDelete","Fee":"2000000","Flags":2147483648,"Destination":"rNp5zaiaR3maZ8zALz5CWnqRYXWkeGhteS","Account":"rwEJf6YSKALUaxRhvJ1S81PPmXzWhDW8on","Sequence":23159180,"LastLedgerSequence":23164152,"SigningPubKey":"02B87CEB1507849B6473773155827C0B8C15CB311C6876FBD7FAB95F06D3E18E39","TxnSignature":"3045"}"#;
+        let keys: Vec<String> = from_str::<Value>(input).unwrap().as_object().unwrap().keys().cloned().collect();
+        let canonical_order = DefinitionFields::global().ordering_fields(keys);
+        for for_signing in [true, false] {
+          let options = SerializeOptions { for_signing, ..Default::default() };
+          let output = serialize_tx_in_order(input.to_string(), &canonical_order, &options, None).unwrap();
+          assert_eq!(output, serialize_tx(input.to_string(), for_signing, None).unwrap());
+        }
+
+        let options = SerializeOptions::default();
+        let input = r#"{"TransactionType":"AccountSet","Sequence":5,"Fee":"12","hash":"00"}"#;
+        let field_order: Vec<String> = ["Sequence", "TransactionType", "Fee"].iter().map(|name| name.to_string()).collect();
+        assert_eq!(serialize_tx_in_order(input.to_string(), &field_order, &options, None), Ok("240000000512000368400000000000000C".to_string()));
+        // a serialized field left out of the order is rejected instead of dropped from the blob
+        let field_order: Vec<String> = ["Sequence", "TransactionType"].iter().map(|name| name.to_string()).collect();
+        assert_eq!(serialize_tx_in_order(input.to_string(), &field_order, &options, None), Err(InvalidFieldValue("Fee".to_string())));
+        let input = r#"{"TransactionType":"AccountSet","Sequence":5,"Fee":"12","TxnSignature":"3045"}"#;
+        let field_order: Vec<String> = ["Sequence", "TransactionType", "Fee"].iter().map(|name| name.to_string()).collect();
+        let signing_options = SerializeOptions { for_signing: true, ..Default::default() };
+        assert_eq!(serialize_tx_in_order(input.to_string(), &field_order, &signing_options, None), Ok("240000000512000368400000000000000C".to_string()));
+        assert_eq!(serialize_tx_in_order(input.to_string(), &field_order, &options, None), Err(InvalidFieldValue("TxnSignature".to_string())));
+        let field_order: Vec<String> = ["Sequence", "TransactionType", "Fee", "Expiration"].iter().map(|name| name.to_string()).collect();
+        assert_eq!(serialize_tx_in_order(input.to_string(), &field_order, &signing_options, None), Err(InvalidFieldValue("Expiration".to_string())));
+        let field_order: Vec<String> = ["Sequence", "TransactionType", "Fee", "Comment"].iter().map(|name| name.to_string()).collect();
+        assert_eq!(serialize_tx_in_order(input.to_string(), &field_order, &signing_options, None), Err(UnknownField("Comment".to_string())));
+        let input = r#"{"TransactionType":"AccountSet","Comment":"rent"}"#;
+        let field_order = vec!["TransactionType".to_string()];
+        assert_eq!(serialize_tx_in_order(input.to_string(), &field_order, &options, None), Err(UnknownField("Comment".to_string())));
+    }
+
+    #[test]
+    fn test_serialize_tx_errors(){
+        let options = SerializeOptions::default();
+        let serialize = |tx: Value| serialize_tx_with_options(tx.to_string(), &options, None);
+        assert!(matches!(serialize_tx_with_options("{\"Fee\":".to_string(), &options, None), Err(InvalidJson(_))));
+        assert!(matches!(serialize(json!(["Payment"])), Err(InvalidJson(_))));
+        assert_eq!(serialize(json!({"Comment": "rent"})), Err(UnknownField("Comment".to_string())));
+        assert_eq!(serialize(json!({"Fee": "1.5"})), Err(InvalidAmount("Fee".to_string())));
+        // more than 100 billion XRP
+        assert_eq!(serialize(json!({"Amount": "100000000000000001"})), Err(InvalidAmount("Amount".to_string())));
+        assert_eq!(serialize(json!({"Amount": "100000000000000000"})), Ok("61416345785D8A0000".to_string()));
+        let amount = json!({"currency": "XRP", "issuer": "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B", "value": "1"});
+        assert_eq!(serialize(json!({"Amount": amount})), Err(InvalidCurrencyCode("XRP".to_string())));
+        let amount = json!({"currency": "USD", "issuer": "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B", "value": "abc"});
+        assert_eq!(serialize(json!({"Amount": amount})), Err(InvalidAmount("Amount".to_string())));
+        let amount = json!({"currency": "USD", "value": "1"});
+        assert_eq!(serialize(json!({"Amount": amount})), Err(MissingAmountKey { field: "Amount".to_string(), key: "issuer".to_string() }));
+        assert_eq!(serialize(json!({"Sequence": 4294967296u64})), Err(FieldOutOfRange { field: "Sequence".to_string(), value: 4294967296 }));
+        assert_eq!(serialize(json!({"SignerWeight": -1})), Err(FieldOutOfRange { field: "SignerWeight".to_string(), value: -1 }));
+        assert_eq!(serialize(json!({"Flags": 4294967296u64})), Err(FieldOutOfRange { field: "Flags".to_string(), value: 4294967296 }));
+        assert_eq!(serialize(json!({"TransferFee": 70000})), Err(FieldOutOfRange { field: "TransferFee".to_string(), value: 70000 }));
+        assert_eq!(serialize(json!({"TickSize": 256})), Err(FieldOutOfRange { field: "TickSize".to_string(), value: 256 }));
+        assert_eq!(serialize(json!({"Sequence": "1"})), Err(InvalidFieldValue("Sequence".to_string())));
+        assert_eq!(serialize(json!({"Sequence": 4294967295u64})), Ok("24FFFFFFFF".to_string()));
+    }
+
+    #[test]
+    fn test_parse_tx_strict(){
+        let input = ACCOUNT_DELETE;
+        let expected = ACCOUNT_DELETE_BLOB;
+        let tx = parse_tx_strict(input).unwrap();
+        assert_eq!(tx, from_str::<Value>(input).unwrap());
+        assert_eq!(serialize_tx_value(&tx, true, None).unwrap(), expected);
+
+        let duplicated = r#"{"TransactionType":"Payment","Fee":"12","Account":"rMdG3ju8pgyVh29ELPWaDuA74CpWW6Fxns","Fee":"1200000"}"#;
+        assert_eq!(parse_tx_strict(duplicated), Err(DuplicateField("Fee".to_string())));
+
+        let nested = r#"{"Memos":[{"Memo":{"MemoData":"72656e74","MemoData":"00"}}]}"#;
+        assert_eq!(parse_tx_strict(nested), Err(DuplicateField("MemoData".to_string())));
+
+        assert!(matches!(parse_tx_strict("{\"Fee\":"), Err(InvalidJson(_))));
+    }
+
+    #[test]
+    fn test_validate_blob_length(){
+        let blob = "1200002200000000240000034A201B009717BE61400000000098968068400000000000000C69D4564B964A845AC0000000000000000000000000555344000000000069D33B18D53385F8A3185516C2EDA5DEDB8AC5C673210379F17CFA0FFD7518181594BE69FE9A10471D6DE1F4055C6D2746AFD6CF89889E811469D33B18D53385F8A3185516C2EDA5DEDB8AC5C6831469D33B18D53385F8A3185516C2EDA5DEDB8AC5C6F9EA7C06636C69656E747D077274312E312E31E1F1011201F3B1997562FD742B54D4EBDEA1D6AEA3D4906B8F100000000000000000000000000000000000000000FF014B4E9C06F24296074F7BC48F92A97916C6DC5EA901DD39C650A96EDA48334E70CC4A85B8B2E8502CD310000000000000000000000000000000000000000000";
+        assert_eq!(validate_blob_length(blob, None), Ok(()));
+
+        let trailing = format!("{}{}", blob, "FF00");
+        assert_eq!(validate_blob_length(&trailing, None), Err(TrailingBytes { remaining: 2 }));
+        let trailing = format!("{}{}", blob, "E1");
+        assert_eq!(validate_blob_length(&trailing, None), Err(TrailingBytes { remaining: 1 }));
+
+        let truncated = &blob[..blob.len() - 4];
+        assert_eq!(validate_blob_length(truncated, None), Err(Truncated));
+        let truncated = "1200152280000000240161618C201B016174F86840000000001E8480732102B87CEB1507849B6473773155827C0B8C15CB311C6876FBD7FAB95F06D3E18E398114656D3E2961EFABDED0C9CDCFB39FC78D01E9A77683";
+        assert_eq!(validate_blob_length(truncated, None), Err(Truncated));
+
+        let nested = "E2".repeat(64);
+        assert_eq!(validate_blob_length(&nested, None), Err(DecodeError("maximum nesting depth exceeded".to_string())));
+
+        // a `SigningPubKey` declaring 929984 bytes, above the 918744 bytes rippled accepts
+        assert_eq!(validate_blob_length("73FEFFFF", None), Err(DecodeError("invalid length prefix".to_string())));
+        assert_eq!(validate_blob_length("73FF", None), Err(DecodeError("invalid length prefix".to_string())));
+    }
+
+    #[test]
+    fn test_path_set_bad_step_type(){
+        let issuer = "F3B1997562FD742B54D4EBDEA1D6AEA3D4906B8F";
+        // `Paths` with a step of an issuer
+        let valid = format!("0112{}{}00", "20", issuer);
+        assert_eq!(validate_blob_length(&valid, None), Ok(()));
+        assert_eq!(deserialize_tx(valid, None), Some(json!({"Paths": [[{"issuer": "rPDXxSZcuVL3ZWoyU82bcde3zwvmShkRyF"}]]})));
+        // no known flag, or an unknown bit besides a known flag
+        for step_type in ["02", "40", "61"] {
+          let blob = format!("0112{}{}00", step_type, issuer);
+          assert_eq!(validate_blob_length(&blob, None), Err(DecodeError("invalid path step type".to_string())), "{}", step_type);
+          assert_eq!(deserialize_tx(blob, None), None, "{}", step_type);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_serialize_from_reader(){
+        let input = ACCOUNT_DELETE;
+        let expected = ACCOUNT_DELETE_BLOB;
+        let options = SerializeOptions { for_signing: true, ..Default::default() };
+        let output = serialize_from_reader(std::io::Cursor::new(input), &options, None);
+        assert_eq!(output.unwrap(), expected);
+        assert!(matches!(serialize_from_reader(std::io::Cursor::new("{"), &options, None), Err(InvalidJson(_))));
+
+        // the options are applied the same as serialize_tx_with_options
+        let options = SerializeOptions { for_signing: true, hex_case: HexCase::Lower, check_required: true, ..Default::default() };
+        assert_eq!(serialize_from_reader(std::io::Cursor::new(input), &options, None).unwrap(), expected.to_lowercase());
+        let missing = r#"{"TransactionType":"AccountDelete","Fee":"2000000","Account":"rwEJf6YSKALUaxRhvJ1S81PPmXzWhDW8on","Sequence":23159180}"#;
+        let missing_destination = MissingField { tx_type: "AccountDelete".to_string(), field: "Destination".to_string() };
+        assert_eq!(serialize_from_reader(std::io::Cursor::new(missing), &options, None), Err(missing_destination));
+    }
+
+    #[cfg(feature = "lenient")]
+    #[test]
+    fn test_serialize_tx_lenient(){
+        let strict = r#"{"TransactionType":"AccountDelete","Fee":"2000000","Flags":2147483648,"Destination":"rNp5zaiaR3maZ8zALz5CWnqRYXWkeGhteS","Account":"rwEJf6YSKALUaxRhvJ1S81PPmXzWhDW8on","Sequence":23159180}"#;
+        let lenient = r#"{
+          // the same transaction with a comment, unquoted keys and a trailing comma
+          TransactionType: "AccountDelete",
+          Fee: "2000000",
+          Flags: 2147483648,
+          Destination: "rNp5zaiaR3maZ8zALz5CWnqRYXWkeGhteS",
+          Account: "rwEJf6YSKALUaxRhvJ1S81PPmXzWhDW8on",
+          Sequence: 23159180,
+        }"#;
+        let options = SerializeOptions { for_signing: true, ..Default::default() };
+        let output = serialize_tx_lenient(lenient.to_string(), &options, None);
+        assert_eq!(output, serialize_tx_with_options(strict.to_string(), &options, None));
+        assert!(output.is_ok());
+        assert_eq!(serialize_tx(lenient.to_string(), true, None), None);
+        assert!(matches!(serialize_tx_lenient("{".to_string(), &options, None), Err(InvalidJson(_))));
+
+        // the options are applied the same as serialize_tx_with_options
+        let options = SerializeOptions { for_signing: true, hex_prefix: true, check_required: true, ..Default::default() };
+        let missing_account = MissingField { tx_type: "AccountDelete".to_string(), field: "Account".to_string() };
+        assert_eq!(serialize_tx_lenient("{TransactionType: 'AccountDelete', Fee: '2000000'}".to_string(), &options, None), Err(missing_account));
+        let strict = serialize_tx_with_options(strict.to_string(), &options, None).unwrap();
+        assert!(strict.starts_with("0x"));
+        assert_eq!(serialize_tx_lenient(lenient.to_string(), &options, None), Ok(strict));
     }
 }
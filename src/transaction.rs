@@ -0,0 +1,245 @@
+//! Typed structures of the common transactions, which are converted into the transaction JSON with the correct field names,
+//! so that a misspelled field is caught at compile time rather than by a failed serialization.
+//!
+//! Only available with the `transaction` feature, the untyped [`serialize_tx`][`crate::serialize::serialize_tx`] supports all transactions.
+
+use alloc::string::String;
+use serde_derive::Serialize;
+use serde_json::{Value, to_value};
+use crate::definition_fields::DefinitionFields;
+use crate::serialize::serialize_tx_value;
+
+/// An amount of XRP in drops, like `"1000000"`, or an amount of an issued currency.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum CurrencyAmount {
+  Xrp(String),
+  Issued(IssuedCurrencyAmount),
+}
+
+/// An amount of an issued currency, the `currency` is a 3 characters code or a 40 characters hex code.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct IssuedCurrencyAmount {
+  pub currency: String,
+  pub issuer: String,
+  pub value: String,
+}
+
+/// The fields shared by all transactions.
+#[derive(Serialize, Debug, Clone, PartialEq, Default)]
+#[serde(rename_all = "PascalCase")]
+pub struct CommonFields {
+  pub account: String,
+  pub fee: String,
+  pub sequence: u32,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub flags: Option<u32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub last_ledger_sequence: Option<u32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub source_tag: Option<u32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub signing_pub_key: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub txn_signature: Option<String>,
+}
+
+/// A `Payment` transaction.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "PascalCase")]
+pub struct Payment {
+  #[serde(flatten)]
+  pub common: CommonFields,
+  pub destination: String,
+  pub amount: CurrencyAmount,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub destination_tag: Option<u32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub send_max: Option<CurrencyAmount>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub deliver_min: Option<CurrencyAmount>,
+  #[serde(rename = "InvoiceID", skip_serializing_if = "Option::is_none")]
+  pub invoice_id: Option<String>,
+  /// The paths in the same JSON as rippled, e.g. `[[{"account": ...}]]`.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub paths: Option<Value>,
+}
+
+/// An `OfferCreate` transaction.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "PascalCase")]
+pub struct OfferCreate {
+  #[serde(flatten)]
+  pub common: CommonFields,
+  pub taker_gets: CurrencyAmount,
+  pub taker_pays: CurrencyAmount,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub expiration: Option<u32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub offer_sequence: Option<u32>,
+}
+
+/// A `TrustSet` transaction.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "PascalCase")]
+pub struct TrustSet {
+  #[serde(flatten)]
+  pub common: CommonFields,
+  pub limit_amount: IssuedCurrencyAmount,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub quality_in: Option<u32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub quality_out: Option<u32>,
+}
+
+/// An `AccountDelete` transaction.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "PascalCase")]
+pub struct AccountDelete {
+  #[serde(flatten)]
+  pub common: CommonFields,
+  pub destination: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub destination_tag: Option<u32>,
+}
+
+/// A typed transaction, converted into the transaction JSON with its `TransactionType`.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "TransactionType")]
+pub enum Transaction {
+  Payment(Payment),
+  OfferCreate(OfferCreate),
+  TrustSet(TrustSet),
+  AccountDelete(AccountDelete),
+}
+
+impl Transaction {
+  /// Convert the transaction into the transaction JSON taken by [`serialize_tx_value`].
+  ///
+  /// # Errors
+  ///  If the transaction can not be converted, `None` will be returned.
+  pub fn to_value(&self) -> Option<Value> {
+    to_value(self).ok()
+  }
+
+  /// Serialize the transaction, the same as [`serialize_tx_value`] of its [`to_value()`][`Transaction::to_value`].
+  ///
+  /// # Example
+  ///
+  ///```
+  ///use rippled_binary_codec::transaction::{AccountDelete, CommonFields, Transaction};
+  ///
+  ///fn transaction_serialize_example(){
+  ///  let tx = Transaction::AccountDelete(AccountDelete {
+  ///    common: CommonFields {
+  ///      account: "rwEJf6YSKALUaxRhvJ1S81PPmXzWhDW8on".to_string(),
+  ///      fee: "2000000".to_string(),
+  ///      sequence: 23159180,
+  ///      signing_pub_key: Some("02B87CEB1507849B6473773155827C0B8C15CB311C6876FBD7FAB95F06D3E18E39".to_string()),
+  ///      ..Default::default()
+  ///    },
+  ///    destination: "rNp5zaiaR3maZ8zALz5CWnqRYXWkeGhteS".to_string(),
+  ///    destination_tag: None,
+  ///  });
+  ///  let serialized = tx.serialize(true, None).unwrap();
+  ///}
+  ///```
+  ///
+  /// # Errors
+  ///  If any field is invalid, like an invalid address, `None` will be returned.
+  pub fn serialize(&self, for_signing: bool, definition_fields: Option<&DefinitionFields>) -> Option<String> {
+    serialize_tx_value(&self.to_value()?, for_signing, definition_fields)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use alloc::string::ToString;
+  use serde_json::{from_str, json};
+  use crate::fixtures::{ACCOUNT_DELETE, ACCOUNT_DELETE_BLOB};
+  use super::*;
+
+  fn common(account: &str, fee: &str, sequence: u32) -> CommonFields {
+    CommonFields { account: account.to_string(), fee: fee.to_string(), sequence, ..Default::default() }
+  }
+
+  #[test]
+  fn test_account_delete() {
+    let tx = Transaction::AccountDelete(AccountDelete {
+      common: CommonFields {
+        flags: Some(2147483648),
+        last_ledger_sequence: Some(23164152),
+        signing_pub_key: Some("02B87CEB1507849B6473773155827C0B8C15CB311C6876FBD7FAB95F06D3E18E39".to_string()),
+        ..common("rwEJf6YSKALUaxRhvJ1S81PPmXzWhDW8on", "2000000", 23159180)
+      },
+      destination: "rNp5zaiaR3maZ8zALz5CWnqRYXWkeGhteS".to_string(),
+      destination_tag: None,
+    });
+    let expected = ACCOUNT_DELETE_BLOB;
+    assert_eq!(tx.serialize(true, None).unwrap(), expected);
+    assert_eq!(tx.to_value().unwrap(), from_str::<Value>(ACCOUNT_DELETE).unwrap());
+  }
+
+  #[test]
+  fn test_payment_and_offer_create() {
+    let usd = IssuedCurrencyAmount {
+      currency: "USD".to_string(),
+      issuer: "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B".to_string(),
+      value: "7072.8".to_string(),
+    };
+    let tx = Transaction::Payment(Payment {
+      common: common("rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys", "10", 5),
+      destination: "rNp5zaiaR3maZ8zALz5CWnqRYXWkeGhteS".to_string(),
+      amount: CurrencyAmount::Issued(usd.clone()),
+      destination_tag: Some(7),
+      send_max: Some(CurrencyAmount::Xrp("15000000000".to_string())),
+      deliver_min: None,
+      invoice_id: None,
+      paths: None,
+    });
+    let value = tx.to_value().unwrap();
+    assert_eq!(value, json!({
+      "TransactionType": "Payment",
+      "Account": "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys",
+      "Fee": "10",
+      "Sequence": 5,
+      "Destination": "rNp5zaiaR3maZ8zALz5CWnqRYXWkeGhteS",
+      "Amount": {"currency": "USD", "issuer": "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B", "value": "7072.8"},
+      "DestinationTag": 7,
+      "SendMax": "15000000000"
+    }));
+    assert_eq!(tx.serialize(false, None), serialize_tx_value(&value, false, None));
+
+    let tx = Transaction::OfferCreate(OfferCreate {
+      common: common("rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys", "10", 1752792),
+      taker_gets: CurrencyAmount::Xrp("15000000000".to_string()),
+      taker_pays: CurrencyAmount::Issued(usd),
+      expiration: Some(595640108),
+      offer_sequence: Some(1752791),
+    });
+    let input = r#"{"TransactionType":"OfferCreate","Account":"rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys","Fee":"10","Sequence":1752792,"TakerGets":"15000000000","TakerPays":{"currency":"USD","issuer":"rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B","value":"7072.8"},"Expiration":595640108,"OfferSequence":1752791}"#;
+    assert_eq!(tx.serialize(false, None).unwrap(), crate::serialize::serialize_tx(input.to_string(), false, None).unwrap());
+  }
+
+  #[test]
+  fn test_trust_set() {
+    let tx = Transaction::TrustSet(TrustSet {
+      common: CommonFields {
+        flags: Some(2147614720),
+        last_ledger_sequence: Some(80410003),
+        signing_pub_key: Some("03F5C5BB1D19EC710D3D7FAD199AF10CF8BC1D11348E5B3765C0B0B9C0BEC32879".to_string()),
+        ..common("rGUmkyLbvqGF3hwX4qwGHdrzLdY2Qpskum", "12", 79991857)
+      },
+      limit_amount: IssuedCurrencyAmount {
+        currency: "534F4C4F00000000000000000000000000000000".to_string(),
+        issuer: "rsoLo2S1kiGeCcn6hCUXVrCpGMWLrRrLZz".to_string(),
+        value: "10000000000".to_string(),
+      },
+      quality_in: None,
+      quality_out: None,
+    });
+    // the transaction of `test_serialize_tx` in the serialize module
+    let expected = "12001422800200002404C49431201B04CAF59363D7038D7EA4C68000534F4C4F000000000000000000000000000000001EB3EAA3AD86242E1D51DC502DD6566BD39E06A668400000000000000C732103F5C5BB1D19EC710D3D7FAD199AF10CF8BC1D11348E5B3765C0B0B9C0BEC328798114A6C3D314FB5418627AB22D9DDF6C18AED5F6CA89";
+    assert_eq!(tx.serialize(true, None).unwrap(), expected);
+  }
+}
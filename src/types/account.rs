@@ -1,9 +1,12 @@
 //! A structure represents `AccountID` type of field in ripple transaction and methods to serialize them to bytes.
 use crate::ripple_address_codec::decode_account_id;
 use serde_json::Value;
-use bytes::{BytesMut, BufMut};
-use crate::{definition_fields::SerializeField};
+use core::convert::TryFrom;
+use bytes::BufMut;
+use crate::definition_fields::{FieldWriter, SerializeField};
 use alloc::vec::Vec;
+use alloc::string::ToString;
+use crate::errors::{Result, RippleBinaryCodecError::{DecodeError, Truncated}};
 
 /// Helper function for length-prefixed fields including `Blob` types
 /// and some `AccountID` types.
@@ -33,35 +36,82 @@ use alloc::vec::Vec;
 /// # Errors
 ///  If the field is failed to encode, `None` will be returned.
 pub fn vl_encode(input: Vec<u8>) -> Option<Vec<u8>>{
-  let mut vl_len: u32 = input.len() as u32;
-  let mut result = BytesMut::with_capacity(0);
+  let mut result = Vec::with_capacity(input.len() + 3);
+  write_vl_prefix(input.len(), &mut FieldWriter::new(&mut result))?;
+  result.extend_from_slice(&input);
+  Some(result)
+}
+
+/// Write the length prefix of a variable length field of `len` bytes.
+pub(crate) fn write_vl_prefix<B: BufMut>(len: usize, out: &mut FieldWriter<B>) -> Option<()> {
+  let mut vl_len: u32 = u32::try_from(len).ok()?;
   if vl_len <= 192 {
-    let byte1: u8 = vl_len.to_be_bytes()[3];
-    result.put_u8(byte1);
-    result.extend_from_slice(&input);
-    return Some(result.to_vec());
+    out.put_u8(vl_len.to_be_bytes()[3])
   }else if vl_len <= 12480 {
     vl_len -= 193;
     let byte1: u32 = (vl_len >> 8) + 193;
     let byte2: u32 = vl_len  & 0xff;
-    result.put_u8(byte1.to_be_bytes()[3]);
-    result.put_u8(byte2.to_be_bytes()[3]);
-    result.extend_from_slice(&input);
-    return Some(result.to_vec());
+    out.put_slice(&[byte1.to_be_bytes()[3], byte2.to_be_bytes()[3]])
   }else if vl_len <=918744 {
     vl_len -= 12481;
     let byte1 = 241 + (vl_len >> 16);
     let byte2 = (vl_len >> 8) & 0xff;
     let byte3: u32= vl_len & 0xff;
-    result.put_u8(byte1.to_be_bytes()[3]);
-    result.put_u8(byte2.to_be_bytes()[3]);
-    result.put_u8(byte3.to_be_bytes()[3]);
-    result.extend_from_slice(&input);
-    return Some(result.to_vec());
+    out.put_slice(&[byte1.to_be_bytes()[3], byte2.to_be_bytes()[3], byte3.to_be_bytes()[3]])
+  }else{
+    None
   }
-  return None;
 }
 
+/// Decodes the length prefix written by [`vl_encode`] at the start of `input`.
+///
+/// Returns the content and the number of bytes consumed, including the 1-3 bytes prefix.
+///
+/// # Example
+///
+///```
+///use rippled_binary_codec::types::account::vl_decode;
+///
+///fn vl_decode_example(){
+///  let (content, consumed) = vl_decode(b"\x03\x01\x02\x03\xe1").unwrap();
+///  assert_eq!(content, vec![1, 2, 3]);
+///  assert_eq!(consumed, 4);
+///}
+///```
+///
+/// # Errors
+///  If the prefix is invalid or `input` is shorter than the length it declares, `None` will be returned.
+pub fn vl_decode(input: &[u8]) -> Option<(Vec<u8>, usize)>{
+  let (vl_len, prefix_len) = vl_prefix(input).ok()?;
+  let content = input.get(prefix_len..prefix_len + vl_len)?;
+  Some((content.to_vec(), prefix_len + vl_len))
+}
+
+/// Decodes the length prefix at the start of `input` into `(content_len, prefix_len)`, without reading the content.
+pub(crate) fn vl_prefix(input: &[u8]) -> Result<(usize, usize)>{
+  let byte1 = usize::from(*input.first().ok_or(Truncated)?);
+  let (vl_len, prefix_len) = if byte1 <= 192 {
+    (byte1, 1)
+  } else if byte1 <= 240 {
+    let byte2 = usize::from(*input.get(1).ok_or(Truncated)?);
+    (193 + (byte1 - 193) * 256 + byte2, 2)
+  } else if byte1 <= 254 {
+    let byte2 = usize::from(*input.get(1).ok_or(Truncated)?);
+    let byte3 = usize::from(*input.get(2).ok_or(Truncated)?);
+    (12481 + (byte1 - 241) * 65536 + byte2 * 256 + byte3, 3)
+  } else {
+    return Err(DecodeError("invalid length prefix".to_string()));
+  };
+  if vl_len > 918744 {
+    return Err(DecodeError("invalid length prefix".to_string()));
+  }
+  Ok((vl_len, prefix_len))
+}
+
+/// The `AccountID` fields which can be an empty string, serialized as an empty VL field. An empty `RegularKey` of a
+/// `SetRegularKey` removes the regular key, the same as leaving it out.
+pub const EMPTY_ACCOUNT_FIELDS: [&str; 1] = ["RegularKey"];
+
 /// A structure represents `AccountID` type of field.
 pub struct Account{
   pub data: Value
@@ -86,10 +136,22 @@ impl SerializeField for Account {
     ///```
     /// # Errors
     ///  If the field is failed to serialize, `None` will be returned.
+    ///
+    ///  An empty string fails here, since it's only valid for the fields of [`EMPTY_ACCOUNT_FIELDS`], which is checked by
+    ///  [`DefinitionFields`][`crate::definition_fields::DefinitionFields`].
     fn to_bytes(&self) -> Option<Vec<u8>>{
-        let account = self.data.as_str()?;
-        let vl_content: [u8;20] = decode_account_id(account).ok()?;
-        vl_encode(vl_content.to_vec())
+        let mut buf = Vec::with_capacity(21);
+        Account::write(&self.data, &mut FieldWriter::new(&mut buf))?;
+        Some(buf)
+    }
+}
+
+impl Account {
+    /// Write an `AccountID` given as a classic address with its length prefix into `out`.
+    pub(crate) fn write<B: BufMut>(data: &Value, out: &mut FieldWriter<B>) -> Option<()> {
+        let vl_content: [u8;20] = decode_account_id(data.as_str()?).ok()?;
+        write_vl_prefix(vl_content.len(), out)?;
+        out.put_slice(&vl_content)
     }
 }
 
@@ -120,6 +182,25 @@ mod tests {
       let vl_content: [u8;20] = decode_account_id(address).unwrap();
       assert_eq!(vl_encode(vl_content.to_vec()).unwrap(), expected);
     }
+    #[test]
+    fn test_vl_decode(){
+      for len in [0, 1, 192, 193, 194, 12480, 12481, 12482, 918744] {
+        let content = vec![0xab; len];
+        let mut encoded = vl_encode(content.clone()).unwrap();
+        let consumed = encoded.len();
+        encoded.push(0xe1);
+        assert_eq!(vl_decode(&encoded), Some((content, consumed)));
+        assert_eq!(vl_decode(&encoded[..consumed - 1]), None);
+      }
+      assert_eq!(vl_decode(b""), None);
+      assert_eq!(vl_decode(b"\xc1"), None);
+      assert_eq!(vl_decode(b"\xff\x00\x00"), None);
+      assert_eq!(vl_prefix(&[0xfe, 0xd4, 0x17]), Ok((918744, 3)));
+      assert_eq!(vl_prefix(&[0xfe, 0xd4, 0x18]), Err(DecodeError("invalid length prefix".to_string())));
+      // 918745 bytes
+      assert_eq!(vl_decode(&[0xfe, 0xd4, 0x18]), None);
+    }
+
     #[test]
     fn test_account_id_to_bytes() {
         let input= json!("rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys");
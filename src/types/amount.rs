@@ -1,21 +1,25 @@
 //! A structure that representing `Amount` type of field in ripple transaction and methods to serialize them to bytes.
 
-use core::convert::TryInto;
+use core::convert::{TryFrom, TryInto};
 use ascii::AsciiStr;
 use bytes::{BytesMut, BufMut};
 use proc_macro_regex::regex;
-use crate::ripple_address_codec::decode_account_id;
-use serde_json::Value;
+use crate::ripple_address_codec::{decode_account_id, encode_account_id};
+use serde_json::{Value, json};
 use rust_decimal::prelude::*;
 use alloc::string::{String,ToString};
 use alloc::vec::Vec;
 
-use crate::definition_fields::SerializeField;
+use crate::definition_fields::{FieldWriter, SerializeField};
 
 const MIN_MANTISSA: i128 = 10i128.pow(15);
 const MAX_MANTISSA: i128 = 10i128.pow(16)-1;
 const MIN_EXP: i32 = -96;
 const MAX_EXP: i32 = 80;
+/// 10^17 drops, the total supply of XRP.
+const MAX_DROPS: i64 = 10i64.pow(17);
+/// The serialized value of a zero issued amount.
+const CANONICAL_ZERO: u64 = 0x8000000000000000;
 
 pub struct IssuedAmount{
   pub strnum: String
@@ -25,51 +29,197 @@ regex!(regex_currency_code_iso_4217 r"^[A-Za-z0-9?!@#$%^&*<>(){}\[\]|]{3}$");
 regex!(regex_currency_code_hex r"^[0-9a-fA-F]{40}$");
 
 impl IssuedAmount {
+  /// Serialize the value of an issued amount to 8 bytes, normalizing it to a 16 digits mantissa and an exponent.
+  ///
+  /// The value is parsed digit by digit rather than into a [`Decimal`], so the whole range of exponents from -96 to 80 is
+  /// supported. A value with more than 16 significant digits, such as the integer `"12345678901234567"`, is rounded half
+  /// away from zero at the 16th digit the same as rippled, to `"12345678901234570"`.
+  /// Trailing zeros do not count as significant digits, so `"10000000000000000000"` is exact.
+  ///
+  /// # Errors
+  ///  If the value is invalid, or it's too large or too small but not zero to be represented after rounding, `None` will be returned.
   pub fn to_bytes(&self)-> Option<Vec<u8>>{
-    let value = Decimal::from_str(self.strnum.as_str()).ok()?;
-    if value.is_zero(){
-      return self.canonical_zero_serial();
-    }
-    let mut mantissa = value.mantissa().abs();
-    let exp: u32 = value.scale();
-    let exp_bytes = exp.to_be_bytes();
-    let mut exp: i32 = i32::from_be_bytes(exp_bytes);
-    exp = exp.overflowing_neg().0;
-    while mantissa < MIN_MANTISSA && exp > MIN_EXP {
-      mantissa *= 10;
-      exp -= 1;
-    }
-    while mantissa > MAX_MANTISSA{
-      if exp >= MAX_EXP {
-        return None; 
-      }
-      mantissa = mantissa / 10;
-      exp += 1;
-    }
-    if exp < MIN_EXP || mantissa < MIN_MANTISSA{
-      return self.canonical_zero_serial();
+    Some(IssuedAmount::value_bits(&self.strnum)?.to_be_bytes().to_vec())
+  }
+
+  /// The 8 bytes of [`to_bytes()`][`IssuedAmount::to_bytes`] as an integer.
+  fn value_bits(strnum: &str) -> Option<u64>{
+    let (is_negative, mantissa, exp) = normalize_decimal(strnum)?;
+    if mantissa == 0 {
+      return Some(CANONICAL_ZERO);
     }
-    if exp > MAX_EXP || mantissa > MAX_MANTISSA{
+    if !(MIN_EXP..=MAX_EXP).contains(&exp) {
       return None;
     }
     let mut result = u64::from_str_radix("8000000000000000", 16).ok()?;
-    if value.is_sign_positive(){
+    if !is_negative{
       result |= u64::from_str_radix("4000000000000000", 16).ok()?;
     }
     let exp: u64 = (exp+97).try_into().ok()?;
     result |= u64::from(exp<<54);
-    result |= mantissa.to_u64()?;
-    return Some(result.to_be_bytes().to_vec());
+    result |= mantissa;
+    return Some(result);
   }
-  fn canonical_zero_serial(&self) -> Option<Vec<u8>>{
-    return hex::decode("8000000000000000").ok();
+  /// Parse the `strnum` into a [`Decimal`], see [`parse_decimal`].
+  fn parse_value(&self) -> Option<Decimal>{
+    parse_decimal(&self.strnum)
+  }
+
+  /// Decode the 8 bytes value of an issued amount, the inverse of [`to_bytes()`][`IssuedAmount::to_bytes`].
+  ///
+  /// The value is rendered in its shortest form, with scientific notation for very large or small numbers the same as
+  /// `ripple-binary-codec`, e.g. `"7072.8"`, `"5e-7"` and `"1.5e+25"`.
+  ///
+  /// # Example
+  ///
+  ///```
+  ///use rippled_binary_codec::types::amount::IssuedAmount;
+  ///
+  ///fn issued_amount_from_bytes_example(){
+  ///  let amount = IssuedAmount::from_bytes(b"\xd4\xc4N\x94\x96\xdcx\x00").unwrap();
+  ///  assert_eq!(amount.strnum, "12.123");
+  ///}
+  ///```
+  ///
+  /// # Errors
+  ///  If `bytes` is not 8 bytes or not an issued amount, `None` will be returned.
+  pub fn from_bytes(bytes: &[u8]) -> Option<IssuedAmount>{
+    let amount = u64::from_be_bytes(bytes.try_into().ok()?);
+    if amount & 0x8000000000000000 == 0 {
+      return None;
+    }
+    let mut mantissa = amount & 0x003FFFFFFFFFFFFF;
+    if mantissa == 0 {
+      return Some(IssuedAmount { strnum: "0".to_string() });
+    }
+    let sign = if amount & 0x4000000000000000 == 0 { "-" } else { "" };
+    let mut exp = ((amount >> 54) & 0xFF) as i32 - 97;
+    while mantissa % 10 == 0 {
+      mantissa /= 10;
+      exp += 1;
+    }
+    let digits = mantissa.to_string();
+    let scientific_exp = exp + digits.len() as i32 - 1;
+    let strnum = if scientific_exp <= -7 || scientific_exp >= 21 {
+      let (first, rest) = digits.split_at(1);
+      let fraction = if rest.is_empty() { String::new() } else { format!(".{}", rest) };
+      let exp_sign = if scientific_exp > 0 { "+" } else { "" };
+      format!("{}{}e{}{}", first, fraction, exp_sign, scientific_exp)
+    } else if exp >= 0 {
+      format!("{}{}", digits, "0".repeat(exp as usize))
+    } else {
+      let point = digits.len() as i32 + exp;
+      if point > 0 {
+        let (integer, fraction) = digits.split_at(point as usize);
+        format!("{}.{}", integer, fraction)
+      } else {
+        format!("0.{}{}", "0".repeat(-point as usize), digits)
+      }
+    };
+    Some(IssuedAmount { strnum: format!("{}{}", sign, strnum) })
   }
+
+  /// Render the value with exactly `decimals` decimal places for display, rounding half away from zero,
+  /// while [`from_bytes()`][`IssuedAmount::from_bytes`] keeps the canonical rendering.
+  ///
+  /// # Example
+  ///
+  ///```
+  ///use rippled_binary_codec::types::amount::IssuedAmount;
+  ///
+  ///fn issued_amount_to_fixed_example(){
+  ///  let amount = IssuedAmount::from_bytes(b"\xd4\xc4N\x94\x96\xdcx\x00").unwrap();
+  ///  assert_eq!(amount.to_fixed(2).unwrap(), "12.12");
+  ///}
+  ///```
+  ///
+  /// # Errors
+  ///  If the value is out of the range of [`Decimal`], like `"1e+80"`, `None` will be returned.
+  pub fn to_fixed(&self, decimals: u32) -> Option<String> {
+    let value = self.parse_value()?.round_dp_with_strategy(decimals, RoundingStrategy::MidpointAwayFromZero);
+    Some(format!("{:.*}", decimals as usize, value))
+  }
+}
+
+/// Parse a decimal string into a [`Decimal`], accepting both `e` and `E` as the exponent marker,
+/// and a leading decimal point like `".5"`.
+fn parse_decimal(strnum: &str) -> Option<Decimal>{
+  let mut strnum = strnum.replace('E', "e");
+  let (sign, unsigned) = match strnum.strip_prefix('-') {
+    Some(unsigned) => ("-", unsigned),
+    None => ("", strnum.as_str()),
+  };
+  if let Some(fraction) = unsigned.strip_prefix('.').filter(|fraction| fraction.starts_with(|c: char| c.is_ascii_digit())) {
+    strnum = format!("{}0.{}", sign, fraction);
+  }
+  if strnum.contains('e') {
+    return Decimal::from_scientific(strnum.as_str()).ok();
+  }
+  Decimal::from_str(strnum.as_str()).ok()
+}
+
+/// Normalize a decimal string to whether it's negative, a 16 digits mantissa and an exponent, the value being the mantissa
+/// times 10 to the power of the exponent. A value with more than 16 significant digits is rounded half away from zero at the
+/// 16th digit. The mantissa of zero is 0, the range of the exponent is checked by the caller.
+pub(crate) fn normalize_decimal(strnum: &str) -> Option<(bool, u64, i32)> {
+  let (is_negative, mut digits, mut exp) = parse_scientific(strnum)?;
+  while digits.last() == Some(&0) {
+    digits.pop();
+    exp = exp.checked_add(1)?;
+  }
+  if digits.is_empty(){
+    return Some((is_negative, 0, 0));
+  }
+  let round_up = digits.len() > 16 && digits[16] >= 5;
+  if digits.len() > 16 {
+    exp = exp.checked_add(i32::try_from(digits.len() - 16).ok()?)?;
+    digits.truncate(16);
+  }
+  let mut mantissa = digits.iter().fold(0i128, |mantissa, digit| mantissa * 10 + i128::from(*digit));
+  if round_up {
+    mantissa += 1;
+  }
+  if mantissa > MAX_MANTISSA {
+    mantissa /= 10;
+    exp = exp.checked_add(1)?;
+  }
+  while mantissa < MIN_MANTISSA {
+    mantissa *= 10;
+    exp = exp.checked_sub(1)?;
+  }
+  Some((is_negative, u64::try_from(mantissa).ok()?, exp))
+}
+
+/// Split a decimal string like `"-1.5e3"` or `".5"` into whether it's negative, its digits with the leading zeros removed and
+/// its exponent, the value being the digits times 10 to the power of the exponent.
+fn parse_scientific(strnum: &str) -> Option<(bool, Vec<u8>, i32)> {
+  let (is_negative, unsigned) = match strnum.strip_prefix('-') {
+    Some(unsigned) => (true, unsigned),
+    None => (false, strnum),
+  };
+  let (number, exp) = match unsigned.split_once(|c| c == 'e' || c == 'E') {
+    Some((number, exp)) => (number, i32::from_str(exp).ok()?),
+    None => (unsigned, 0),
+  };
+  let (integer, fraction) = number.split_once('.').unwrap_or((number, ""));
+  if integer.is_empty() && fraction.is_empty() {
+    return None;
+  }
+  if !integer.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit()) {
+    return None;
+  }
+  let digits = integer.bytes().chain(fraction.bytes()).map(|c| c - b'0').skip_while(|digit| *digit == 0).collect();
+  let exp = exp.checked_sub(i32::try_from(fraction.len()).ok()?)?;
+  Some((is_negative, digits, exp))
 }
 
 /// Serializes a currency to bytes
 ///
-/// - If the input is "XRP", and `xrp_ok` is true, it will return a 20 zero bytes.
+/// - If the input is "XRP" in any casing, like "xrp", and `xrp_ok` is true, it will return a 20 zero bytes. It's never serialized
+///   as a standard code, so an issued amount, which has an issuer, can not be of `XRP`.
 /// - Otherwise, it will serialize the code by [`AsciiStr::from_ascii`][`from_ascii()`] with leading and trailing zero.
+/// - A 40 characters hex code is decoded as is, but if its first byte is 0x00, it must be 20 zero bytes with `xrp_ok` being true,
+///   or a standard code other than `XRP` in any casing with leading and trailing zero.
 ///
 /// [`from_ascii()`]: https://docs.rs/ascii/1.0.0/ascii/struct.AsciiStr.html#method.from_ascii
 ///
@@ -87,37 +237,245 @@ impl IssuedAmount {
 /// # Errors
 ///  If the field is failed to serialize, `None` will be returned.
 pub fn currency_code_to_bytes(input: &str, xrp_ok: bool) -> Option<Vec<u8>>{
+  currency_code(input, xrp_ok).map(|code| code.to_vec())
+}
+
+/// The 20 bytes of [`currency_code_to_bytes`] on the stack.
+pub(crate) fn currency_code(input: &str, xrp_ok: bool) -> Option<[u8; 20]>{
+  let mut input_slice = [0u8; 20];
   if regex_currency_code_iso_4217(input) {
-    if input == "XRP"{
+    if input.eq_ignore_ascii_case("XRP"){
       if xrp_ok {
-        return Some([0u8;20].to_vec());
+        return Some(input_slice);
       }else{
         return None;
       }
     }else{
-      let mut result = BytesMut::with_capacity(20);
-      result.extend_from_slice(&[0u8;12]);
-      let input_slice = AsciiStr::from_ascii(input).map(|r| r.as_bytes().to_vec()).ok()?;
-      result.extend_from_slice(&input_slice);
-      result.extend_from_slice(&[0u8;5]);
-      return Some(result.to_vec());
+      input_slice[12..15].copy_from_slice(AsciiStr::from_ascii(input).ok()?.as_bytes());
+      return Some(input_slice);
     }
   }else if regex_currency_code_hex(input){
-    let input_slice = hex::decode(input).ok()?;
+    hex::decode_to_slice(input, &mut input_slice).ok()?;
+    // a code starting with 0x00 is in the standard code space, it must be XRP or a valid standard code other than XRP
+    if input_slice[0] == 0 {
+      if input_slice.iter().all(|byte| *byte == 0) {
+        return if xrp_ok { Some(input_slice) } else { None };
+      }
+      let code = AsciiStr::from_ascii(&input_slice[12..15]).ok()?.as_str();
+      let is_standard = input_slice[..12].iter().chain(&input_slice[15..]).all(|byte| *byte == 0);
+      if !is_standard || !regex_currency_code_iso_4217(code) || code.eq_ignore_ascii_case("XRP") {
+        return None;
+      }
+    }
     return Some(input_slice);
   }
   return None;
 }
 
+/// Deserializes a 20 bytes currency code, the inverse of [`currency_code_to_bytes`].
+///
+/// - 20 zero bytes are `"XRP"`.
+/// - A standard currency code is returned as its 3 characters, e.g. `"USD"`.
+/// - Otherwise, the code is returned as 40 characters uppercase hex.
+///
+/// # Example
+///
+///```
+///use rippled_binary_codec::types::amount::currency_code_from_bytes;
+///
+///fn currency_code_from_bytes_example(){
+///  let code = currency_code_from_bytes(b"\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00USD\x00\x00\x00\x00\x00").unwrap();
+///  assert_eq!(code, "USD");
+///}
+///```
+///
+/// # Errors
+///  If `bytes` is not 20 bytes, `None` will be returned.
+pub fn currency_code_from_bytes(bytes: &[u8]) -> Option<String>{
+  if bytes.len() != 20 {
+    return None;
+  }
+  if bytes.iter().all(|byte| *byte == 0) {
+    return Some("XRP".to_string());
+  }
+  let code = &bytes[12..15];
+  let is_standard = bytes[..12].iter().chain(&bytes[15..]).all(|byte| *byte == 0);
+  if is_standard && !code.eq_ignore_ascii_case(b"XRP") {
+    if let Ok(code) = AsciiStr::from_ascii(code) {
+      if regex_currency_code_iso_4217(code.as_str()) {
+        return Some(code.to_string());
+      }
+    }
+  }
+  Some(hex::encode_upper(bytes))
+}
+
+/// Returns the 20 bytes canonical form of a currency code, so that currencies can be compared regardless of their
+/// textual representation, e.g. `"USD"` and `"0000000000000000000000005553440000000000"` are the same currency.
+///
+/// `"XRP"` is canonicalized to 20 zero bytes.
+///
+/// # Example
+///
+///```
+///use rippled_binary_codec::types::amount::canonical_currency;
+///
+///fn canonical_currency_example(){
+///  let usd = canonical_currency("USD").unwrap();
+///  let usd_hex = canonical_currency("0000000000000000000000005553440000000000").unwrap();
+///  assert_eq!(usd, usd_hex);
+///}
+///```
+///
+/// # Errors
+///  If the input is not a valid currency code, `None` will be returned.
+pub fn canonical_currency(code: &str) -> Option<[u8; 20]>{
+  currency_code(code, true)
+}
+
+/// Check whether two `Amount` values are the same once serialized, e.g. `"12.1"` and `"12.10"` of the same
+/// currency and issuer are equal, while the same value in different currencies is not.
+///
+/// # Example
+///
+///```
+///use rippled_binary_codec::types::amount::amounts_equal_canonically;
+///use serde_json::json;
+///
+///fn amounts_equal_canonically_example(){
+///  let a = json!({"currency": "USD", "value": "12.1", "issuer": "rf1BiGeXwwQoi8Z2ueFYTEXSwuJYfV2Jpn"});
+///  let b = json!({"currency": "USD", "value": "12.10", "issuer": "rf1BiGeXwwQoi8Z2ueFYTEXSwuJYfV2Jpn"});
+///  assert!(amounts_equal_canonically(&a, &b));
+///}
+///```
+///
+/// # Errors
+///  If either amount is failed to serialize, `false` will be returned.
+pub fn amounts_equal_canonically(a: &Value, b: &Value) -> bool {
+  match (Amount{data: a.clone()}.to_bytes(), Amount{data: b.clone()}.to_bytes()) {
+    (Some(a), Some(b)) => a == b,
+    _ => false,
+  }
+}
+
+/// Converts an XRP amount in drops to a human readable XRP value, 1 XRP equals 1,000,000 drops.
+///
+/// Trailing zeros of the fractional part are trimmed.
+///
+/// # Example
+///
+///```
+///use rippled_binary_codec::types::amount::drops_to_xrp;
+///
+///fn drops_to_xrp_example(){
+///  let xrp = drops_to_xrp("15000000000").unwrap();
+///  println!("xrp: {}", xrp); // "15000"
+///}
+///```
+///
+/// # Errors
+///  If the input is not a valid drops amount, `None` will be returned.
+pub fn drops_to_xrp(drops: &str) -> Option<String>{
+  let drops = u64::from_str(drops).ok()?;
+  let xrp = Decimal::from_i128_with_scale(i128::from(drops), 6).normalize();
+  Some(xrp.to_string())
+}
+
 /// A structure that representing `Amount` type of field
 pub struct Amount{
   pub data: Value
 }
+impl Amount {
+  /// Serialize XRP drops, which can be negative like the balance changes in the metadata of a transaction:
+  /// the top bit is 0 for XRP, the next bit is 1 for a positive amount or zero, followed by 62 bits of the absolute drops.
+  ///
+  /// # Example
+  ///
+  ///```
+  ///use rippled_binary_codec::types::amount::Amount;
+  ///
+  ///fn xrp_to_bytes_example(){
+  ///  let bytes = Amount::xrp_to_bytes(-10).unwrap();
+  ///  assert_eq!(bytes, b"\x00\x00\x00\x00\x00\x00\x00\x0a");
+  ///}
+  ///```
+  ///
+  /// # Errors
+  ///  If the absolute drops are more than 10^17, the total supply of XRP, `None` will be returned.
+  pub fn xrp_to_bytes(drops: i64) -> Option<Vec<u8>> {
+    let magnitude = drops.checked_abs()?;
+    if magnitude > MAX_DROPS {
+      return None;
+    }
+    let sign = if drops >= 0 { 0x4000000000000000 } else { 0 };
+    let mut buf = BytesMut::with_capacity(8);
+    buf.put_u64(sign | magnitude as u64);
+    Some(buf.to_vec())
+  }
+
+  /// Return the length of the serialized `Amount` at the start of `bytes`, 8 bytes for XRP, 33 bytes for an MPT
+  /// or 48 bytes for an issued currency, told by the flags of the first byte.
+  ///
+  /// `None` will be returned if `bytes` is empty.
+  pub fn len_of(bytes: &[u8]) -> Option<usize> {
+    let first = *bytes.first()?;
+    if first & 0x80 != 0 {
+      Some(48)
+    } else if first & 0x20 != 0 {
+      Some(33)
+    } else {
+      Some(8)
+    }
+  }
+
+  /// Deserialize an `Amount` field from bytes, the inverse of [`to_bytes()`][`Amount::to_bytes`]:
+  /// - XRP: 8 bytes, deserialized to the amount of drops as a string, e.g. `"5973490832"`.
+  /// - MPT: 33 bytes, deserialized to an object of `mpt_issuance_id` and `value`.
+  /// - issued currency: 48 bytes, deserialized to an object of `currency`, `issuer` and `value`, see [`IssuedAmount::from_bytes`].
+  ///
+  /// # Example
+  ///
+  ///```
+  ///use rippled_binary_codec::types::amount::Amount;
+  ///
+  ///fn amount_from_bytes_example(){
+  ///  let amount = Amount::from_bytes(b"@\x00\x00\x01d\x0c<\x90").unwrap();
+  ///  assert_eq!(amount, "5973490832");
+  ///}
+  ///```
+  ///
+  /// # Errors
+  ///  If `bytes` is not a valid `Amount`, `None` will be returned.
+  pub fn from_bytes(bytes: &[u8]) -> Option<Value> {
+    if bytes.len() != Amount::len_of(bytes)? {
+      return None;
+    }
+    if bytes[0] & 0xA0 == 0x20 {
+      let value = u64::from_be_bytes(bytes[1..9].try_into().ok()?);
+      let sign = if bytes[0] & 0x40 == 0 && value != 0 { "-" } else { "" };
+      return Some(json!({"mpt_issuance_id": hex::encode_upper(&bytes[9..]), "value": format!("{}{}", sign, value)}));
+    }
+    if bytes[0] & 0x80 == 0 {
+      let amount = u64::from_be_bytes(bytes.try_into().ok()?);
+      let drops = amount & 0x3FFFFFFFFFFFFFFF;
+      let sign = if amount & 0x4000000000000000 == 0 && drops != 0 { "-" } else { "" };
+      return Some(Value::from(format!("{}{}", sign, drops)));
+    }
+    let value = IssuedAmount::from_bytes(&bytes[..8])?.strnum;
+    let currency = currency_code_from_bytes(&bytes[8..28])?;
+    let issuer = encode_account_id(bytes[28..48].try_into().ok()?);
+    Some(json!({"currency": currency, "issuer": issuer, "value": value}))
+  }
+}
+
 impl SerializeField for Amount {
   ///Serializes an "Amount" type, which can be either `XRP` or an `issued currency`:
-  /// - XRP: 64 bits; 0, followed by 1 ("is positive"), followed by 62 bit UInt amount.
+  /// - XRP: 64 bits; 0, followed by 1 ("is positive"), followed by 62 bit UInt amount, see [`xrp_to_bytes()`][`Amount::xrp_to_bytes`].
+  ///   The drops can be given as a string or an integer JSON number, from 0 to 10^17.
   /// - Issued Currency: 64 bits of amount, followed by 160 bit currency code and
   /// 160 bit issuer `AccountID`.
+  /// - MPT: given as an object of `mpt_issuance_id` and `value`, 8 bits of `0x60` ("is MPT" and "is positive"), followed by
+  ///   the 64 bit UInt `value` up to `0x7FFFFFFFFFFFFFFF` and the 192 bit `mpt_issuance_id`.
   ///
   /// # Example
   ///
@@ -145,54 +503,60 @@ impl SerializeField for Amount {
   /// # Errors
   ///  If the field is failed to serialize, `None` will be returned.
   fn to_bytes(&self) -> Option<Vec<u8>> {
-    if let Some(input) = self.data.as_str() {
-      if let Ok(mut amount) = i64::from_str(input){
-        let mut buf = BytesMut::with_capacity(0);
-        let base: i64 = 10;
-        if amount >= 0 && amount <= base.pow(17) {
-          amount |= i64::from_str_radix("4000000000000000", 16).ok()?;
-        }
-        if amount < 0 && amount >= -base.pow(17){
-          amount = amount .overflowing_neg().0;
-        }
-        buf.put_i64(amount);
-        return Some(buf.to_vec());
+    let mut buf = Vec::with_capacity(48);
+    Amount::write(&self.data, &mut FieldWriter::new(&mut buf))?;
+    Some(buf)
+  }
+}
+
+impl Amount {
+  /// Write an XRP, issued currency or MPT amount into `out`, see [`to_bytes()`][`Amount::to_bytes`].
+  pub(crate) fn write<B: BufMut>(data: &Value, out: &mut FieldWriter<B>) -> Option<()> {
+    let drops = match data {
+      Value::String(input) => i64::from_str(input).ok(),
+      // some tools give XRP drops as a JSON number, which must be an integer
+      Value::Number(input) => input.as_i64(),
+      _ => None,
+    };
+    if let Some(amount) = drops {
+      // an amount of a transaction is never negative
+      if !(0..=MAX_DROPS).contains(&amount) {
+        return None;
       }
-    }else if let Some(obj) = self.data.as_object(){
-      let mut keys: Vec<String> = obj.keys().map(|item| item.to_string()).collect();
-      keys.sort();
-      let currency= keys.get(0)?;
-      let issuer= keys.get(1)?;
-      let value= keys.get(2)?;
-      if currency.eq(&"currency") && issuer.eq(&"issuer") && value.eq(&"value"){
-        if let Some(strnum) = obj.get("value"){
-          let strnum = strnum.as_str()?;
-          let issued_amt = IssuedAmount {
-            strnum: strnum.to_string()
-          };
-          let mut result = BytesMut::with_capacity(0);
-          let issue_amount = issued_amt.to_bytes()?;
-          let currency = obj.get(currency)?;
-          let currency = currency.as_str()?;
-          let currency_code = currency_code_to_bytes(currency, false)?;
-          let address = obj.get(issuer)?;
-          let address = address.as_str()?;
-          let address = decode_account_id(address).ok()?;
-          result.extend_from_slice(&issue_amount);
-          result.extend_from_slice(&currency_code);
-          result.extend_from_slice(&address);
-          return Some(result.to_vec());
-        };
+      return out.put_u64(0x4000000000000000 | amount as u64);
+    }else if let Some(obj) = data.as_object(){
+      if let Some(issuance_id) = obj.get("mpt_issuance_id") {
+        return write_mpt_amount(obj.get("value")?.as_str()?, issuance_id.as_str()?, out);
       }
-      return None;
+      // the keys are looked up by name, any other key of the object is ignored
+      let value = IssuedAmount::value_bits(obj.get("value")?.as_str()?)?;
+      let currency = currency_code(obj.get("currency")?.as_str()?, false)?;
+      let issuer = decode_account_id(obj.get("issuer")?.as_str()?).ok()?;
+      out.put_u64(value)?;
+      out.put_slice(&currency)?;
+      return out.put_slice(&issuer);
     }
     return None;
   }
 }
 
+/// Write an MPT amount of `value` of the MPT issuance `issuance_id`.
+fn write_mpt_amount<B: BufMut>(value: &str, issuance_id: &str, out: &mut FieldWriter<B>) -> Option<()> {
+  if value.is_empty() || !value.chars().all(|c| c.is_ascii_digit()) {
+    return None;
+  }
+  let value = u64::from_str(value).ok().filter(|value| *value <= i64::MAX as u64)?;
+  let mut id = [0u8; 24];
+  hex::decode_to_slice(issuance_id, &mut id).ok()?;
+  out.put_u8(0x60)?;
+  out.put_u64(value)?;
+  out.put_slice(&id)
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json::json;
+    use crate::fixtures::XorShift;
     use super::*;
 
     #[test]
@@ -216,6 +580,52 @@ mod tests {
         let expected3 =  b"@\x00\x00\x00\x1d\xcda\x18";
         assert_eq!(output3.unwrap(), expected3);
     }
+
+    #[test]
+    fn test_issued_amount_object_keys(){
+        let expected = Amount{data: json!({"currency": "USD", "issuer": "rf1BiGeXwwQoi8Z2ueFYTEXSwuJYfV2Jpn", "value": "12.123"})}.to_bytes().unwrap();
+        // the keys may come in any order along with other keys
+        let input = json!({"value": "12.123", "note": "rent", "issuer": "rf1BiGeXwwQoi8Z2ueFYTEXSwuJYfV2Jpn", "currency": "USD"});
+        assert_eq!(Amount{data: input}.to_bytes().unwrap(), expected);
+        let input = json!({"value": "12.123", "currency": "USD", "note": "rf1BiGeXwwQoi8Z2ueFYTEXSwuJYfV2Jpn"});
+        assert_eq!(Amount{data: input}.to_bytes(), None);
+    }
+
+    #[test]
+    fn test_xrp_amount_number_to_bytes(){
+        let output = Amount{data: json!(5973490832u64)}.to_bytes();
+        assert_eq!(output.unwrap(), b"@\x00\x00\x01d\x0c<\x90");
+        assert_eq!(Amount{data: json!(1.5)}.to_bytes(), None);
+        assert_eq!(Amount{data: json!(u64::MAX)}.to_bytes(), None);
+    }
+
+    #[test]
+    fn test_xrp_amount_upper_boundary_to_bytes(){
+        // 10^17 drops, the total supply of XRP
+        let output1 = Amount{data: json!("100000000000000000")}.to_bytes();
+        assert_eq!(hex::encode_upper(output1.unwrap()), "416345785D8A0000");
+
+        let output2 = Amount{data: json!("99999999999999999")}.to_bytes();
+        assert_eq!(hex::encode_upper(output2.unwrap()), "416345785D89FFFF");
+
+        assert_eq!(Amount{data: json!("100000000000000001")}.to_bytes(), None);
+        assert_eq!(Amount{data: json!("-1")}.to_bytes(), None);
+        assert_eq!(Amount{data: json!(-1)}.to_bytes(), None);
+    }
+
+    #[test]
+    fn test_xrp_to_bytes(){
+        assert_eq!(hex::encode_upper(Amount::xrp_to_bytes(0).unwrap()), "4000000000000000");
+        assert_eq!(hex::encode_upper(Amount::xrp_to_bytes(10).unwrap()), "400000000000000A");
+        assert_eq!(hex::encode_upper(Amount::xrp_to_bytes(-10).unwrap()), "000000000000000A");
+        assert_eq!(hex::encode_upper(Amount::xrp_to_bytes(-100000000000000000).unwrap()), "016345785D8A0000");
+        assert_eq!(Amount::xrp_to_bytes(-100000000000000001), None);
+        assert_eq!(Amount::xrp_to_bytes(i64::MIN), None);
+        for drops in [0, 10, -10, -100000000000000000] {
+          let bytes = Amount::xrp_to_bytes(drops).unwrap();
+          assert_eq!(Amount::from_bytes(&bytes).unwrap(), drops.to_string());
+        }
+    }
     #[test]
     fn test_currency_code_to_bytes(){
         let output1= currency_code_to_bytes("USD", false);
@@ -223,6 +633,77 @@ mod tests {
         assert_eq!(output1.unwrap(), expected1);
     }
 
+    #[test]
+    fn test_currency_code_xrp_casing_to_bytes(){
+        for code in ["XRP", "xrp", "Xrp"] {
+          assert_eq!(currency_code_to_bytes(code, true).unwrap(), [0u8; 20]);
+          assert_eq!(currency_code_to_bytes(code, false), None);
+          let amount = json!({"currency": code, "issuer": "rf1BiGeXwwQoi8Z2ueFYTEXSwuJYfV2Jpn", "value": "1"});
+          assert_eq!(Amount{data: amount}.to_bytes(), None);
+        }
+        // the standard code of `xrp` in hex
+        assert_eq!(currency_code_to_bytes("0000000000000000000000007872700000000000", true), None);
+        assert_eq!(currency_code_from_bytes(&hex::decode("0000000000000000000000007872700000000000").unwrap()).unwrap(), "0000000000000000000000007872700000000000");
+    }
+
+    #[test]
+    fn test_currency_code_hex_to_bytes(){
+        let solo = "534F4C4F00000000000000000000000000000000";
+        assert_eq!(hex::encode_upper(currency_code_to_bytes(solo, false).unwrap()), solo);
+        let usd = "0000000000000000000000005553440000000000";
+        assert_eq!(currency_code_to_bytes(usd, false), currency_code_to_bytes("USD", false));
+        let xrp = "0000000000000000000000000000000000000000";
+        assert_eq!(currency_code_to_bytes(xrp, true).unwrap(), [0u8; 20]);
+        assert_eq!(currency_code_to_bytes(xrp, false), None);
+        // not in the standard layout, or the reserved `XRP` code
+        assert_eq!(currency_code_to_bytes("0001000000000000000000005553440000000000", false), None);
+        assert_eq!(currency_code_to_bytes("0000000000000000000000005553440000000001", false), None);
+        assert_eq!(currency_code_to_bytes("0000000000000000000000005852500000000000", true), None);
+        assert_eq!(currency_code_to_bytes("0000000000000000000000000000010000000000", false), None);
+
+        // the standard codes of lowercase and special characters round trip
+        for code in ["usd", "a?!", "$$$", "{|}"] {
+          let bytes = currency_code_to_bytes(code, false).unwrap();
+          assert_eq!(currency_code_from_bytes(&bytes).unwrap(), code);
+        }
+    }
+
+    #[test]
+    fn test_canonical_currency(){
+        let usd = canonical_currency("USD").unwrap();
+        assert_eq!(usd, canonical_currency("0000000000000000000000005553440000000000").unwrap());
+        assert_eq!(usd, canonical_currency("0000000000000000000000005553440000000000".to_lowercase().as_str()).unwrap());
+        assert_ne!(usd, canonical_currency("EUR").unwrap());
+        assert_eq!(canonical_currency("XRP").unwrap(), [0u8; 20]);
+        assert_eq!(canonical_currency("US"), None);
+    }
+
+    #[test]
+    fn test_amounts_equal_canonically(){
+        let usd = |value: &str| json!({"currency": "USD", "value": value, "issuer": "rf1BiGeXwwQoi8Z2ueFYTEXSwuJYfV2Jpn"});
+        assert!(amounts_equal_canonically(&usd("12.1"), &usd("12.10")));
+        assert!(amounts_equal_canonically(&usd("12.1"), &usd("1.21e1")));
+        assert!(!amounts_equal_canonically(&usd("12.1"), &usd("12.11")));
+
+        let eur = json!({"currency": "EUR", "value": "12.1", "issuer": "rf1BiGeXwwQoi8Z2ueFYTEXSwuJYfV2Jpn"});
+        assert!(!amounts_equal_canonically(&usd("12.1"), &eur));
+
+        assert!(amounts_equal_canonically(&json!("1000"), &json!("1000")));
+        assert!(!amounts_equal_canonically(&json!("1000"), &usd("1000")));
+        assert!(!amounts_equal_canonically(&json!("invalid"), &json!("invalid")));
+    }
+
+    #[test]
+    fn test_drops_to_xrp(){
+        assert_eq!(drops_to_xrp("1000000").unwrap(), "1");
+        assert_eq!(drops_to_xrp("1500000").unwrap(), "1.5");
+        assert_eq!(drops_to_xrp("15000000000").unwrap(), "15000");
+        assert_eq!(drops_to_xrp("1").unwrap(), "0.000001");
+        assert_eq!(drops_to_xrp("0").unwrap(), "0");
+        assert_eq!(drops_to_xrp("1.5"), None);
+        assert_eq!(drops_to_xrp("-1"), None);
+    }
+
     #[test]
     fn test_issued_amount_to_bytes() {
         let input1 = IssuedAmount{
@@ -243,4 +724,221 @@ mod tests {
         let expected3 = b"\x94\xc4N\x94\x96\xdcx\x00";
         assert_eq!(input3.to_bytes().unwrap(), expected3);
     }
+
+    #[test]
+    fn test_issued_amount_from_bytes() {
+        let output1 = IssuedAmount::from_bytes(b"\xd4\xc4N\x94\x96\xdcx\x00").unwrap();
+        assert_eq!(output1.strnum, "12.123");
+
+        let output2 = IssuedAmount::from_bytes(b"\x80\x00\x00\x00\x00\x00\x00\x00").unwrap();
+        assert_eq!(output2.strnum, "0");
+        assert_eq!(output2.to_bytes().unwrap(), b"\x80\x00\x00\x00\x00\x00\x00\x00");
+
+        let output3 = IssuedAmount::from_bytes(b"\x94\xc4N\x94\x96\xdcx\x00").unwrap();
+        assert_eq!(output3.strnum, "-12.123");
+
+        for strnum in ["7072.8", "1000000", "0.000001", "5e-7", "1.5e+25", "-1e-15", "9999999999999999"] {
+          let bytes = IssuedAmount{strnum: strnum.to_string()}.to_bytes().unwrap();
+          assert_eq!(IssuedAmount::from_bytes(&bytes).unwrap().strnum, strnum);
+        }
+
+        // XRP amount
+        assert!(IssuedAmount::from_bytes(b"@\x00\x00\x01d\x0c<\x90").is_none());
+        assert!(IssuedAmount::from_bytes(b"\x80\x00").is_none());
+    }
+
+    #[test]
+    fn test_amount_from_bytes(){
+        let input1 = json!({
+        "currency" : "USD",
+        "value" : "12.123",
+        "issuer" : "rf1BiGeXwwQoi8Z2ueFYTEXSwuJYfV2Jpn"
+        });
+        let bytes1 = Amount{data: input1.clone()}.to_bytes().unwrap();
+        assert_eq!(Amount::from_bytes(&bytes1).unwrap(), input1);
+
+        let input2 = json!({
+        "currency" : "534F4C4F00000000000000000000000000000000",
+        "value" : "0",
+        "issuer" : "rf1BiGeXwwQoi8Z2ueFYTEXSwuJYfV2Jpn"
+        });
+        let bytes2 = Amount{data: input2.clone()}.to_bytes().unwrap();
+        assert_eq!(&bytes2[..8], b"\x80\x00\x00\x00\x00\x00\x00\x00");
+        assert_eq!(Amount::from_bytes(&bytes2).unwrap(), input2);
+
+        assert_eq!(Amount::from_bytes(b"@\x00\x00\x01d\x0c<\x90").unwrap(), "5973490832");
+        assert_eq!(Amount::from_bytes(b"\x00\x00\x00\x00\x00\x00\x00\x0a").unwrap(), "-10");
+        assert_eq!(Amount::from_bytes(&bytes1[..47]), None);
+        assert_eq!(Amount::from_bytes(b"@\x00\x00\x01"), None);
+        assert_eq!(Amount::from_bytes(b""), None);
+    }
+
+    #[test]
+    fn test_issued_amount_to_fixed(){
+        let amount = |strnum: &str| IssuedAmount { strnum: strnum.to_string() };
+        let decoded = IssuedAmount::from_bytes(&Amount{data: json!({"currency": "USD", "issuer": "rf1BiGeXwwQoi8Z2ueFYTEXSwuJYfV2Jpn", "value": "7072.8"})}.to_bytes().unwrap()[..8]).unwrap();
+        assert_eq!(decoded.strnum, "7072.8");
+        assert_eq!(decoded.to_fixed(2).unwrap(), "7072.80");
+        assert_eq!(amount("12.125").to_fixed(2).unwrap(), "12.13");
+        assert_eq!(amount("-12.125").to_fixed(2).unwrap(), "-12.13");
+        assert_eq!(amount("5e-7").to_fixed(2).unwrap(), "0.00");
+        assert_eq!(amount("1.5e+5").to_fixed(0).unwrap(), "150000");
+        assert_eq!(amount("1e+80").to_fixed(2), None);
+    }
+
+    #[test]
+    fn test_amount_from_bytes_issuer(){
+        // the `LimitAmount` of the `TrustSet` in `test_serialize_tx` of the serialize module
+        let bytes = hex::decode("D7038D7EA4C68000534F4C4F000000000000000000000000000000001EB3EAA3AD86242E1D51DC502DD6566BD39E06A6").unwrap();
+        let output = Amount::from_bytes(&bytes).unwrap();
+        assert_eq!(output["issuer"], "rsoLo2S1kiGeCcn6hCUXVrCpGMWLrRrLZz");
+        assert_eq!(output["currency"], "534F4C4F00000000000000000000000000000000");
+        assert!(crate::ripple_address_codec::is_valid_classic_address(output["issuer"].as_str().unwrap()));
+        assert_eq!(Amount{data: output}.to_bytes().unwrap(), bytes);
+    }
+
+    #[test]
+    fn test_mpt_amount_to_bytes(){
+        // the MPT amount vectors of the data-driven tests of ripple-binary-codec in xrpl.js
+        let issuance_id = "00002403C84A0A28E0190E208E982C352BBD5006600555CF";
+        let vectors = [
+          ("100", "600000000000000064"),
+          ("9223372036854775807", "607FFFFFFFFFFFFFFF"),
+          ("0", "600000000000000000"),
+        ];
+        for (value, expected) in vectors.iter() {
+          let input = json!({"mpt_issuance_id": issuance_id, "value": value});
+          let output = Amount{data: input.clone()}.to_bytes().unwrap();
+          assert_eq!(hex::encode_upper(&output), format!("{}{}", expected, issuance_id));
+          assert_eq!(Amount::len_of(&output), Some(33));
+          assert_eq!(Amount::from_bytes(&output).unwrap(), input);
+        }
+
+        let output = Amount{data: json!({"mpt_issuance_id": issuance_id, "value": "100"})}.to_bytes().unwrap();
+        let mpt = |value: &str| Amount{data: json!({"mpt_issuance_id": issuance_id, "value": value})}.to_bytes();
+        assert_eq!(mpt("9223372036854775808"), None);
+        assert_eq!(mpt("-1"), None);
+        assert_eq!(mpt("1.5"), None);
+        assert_eq!(mpt(""), None);
+        assert_eq!(Amount{data: json!({"mpt_issuance_id": "00000004A407AF58", "value": "1"})}.to_bytes(), None);
+        assert_eq!(Amount::from_bytes(&output[..32]), None);
+    }
+
+    #[test]
+    fn test_currency_code_from_bytes(){
+        for code in ["USD", "XRP", "534F4C4F00000000000000000000000000000000", "a?!"] {
+          let bytes = currency_code_to_bytes(code, true).unwrap();
+          assert_eq!(currency_code_from_bytes(&bytes).unwrap(), code);
+        }
+        // "XRP" is not allowed as a standard currency code
+        let xrp = hex::decode("0000000000000000000000005852500000000000").unwrap();
+        assert_eq!(currency_code_from_bytes(&xrp).unwrap(), "0000000000000000000000005852500000000000");
+        assert_eq!(currency_code_from_bytes(&xrp[..19]), None);
+    }
+
+    #[test]
+    fn test_issued_amount_exponent_to_bytes() {
+        let lower = IssuedAmount{
+          strnum: "1e10".to_string()
+        }.to_bytes().unwrap();
+        let upper = IssuedAmount{
+          strnum: "1E10".to_string()
+        }.to_bytes().unwrap();
+        let plain = IssuedAmount{
+          strnum: "10000000000".to_string()
+        }.to_bytes().unwrap();
+        assert_eq!(lower, upper);
+        assert_eq!(upper, plain);
+        assert_eq!(hex::encode(upper), "d7038d7ea4c68000");
+    }
+
+    #[test]
+    fn test_issued_amount_leading_decimal_point_to_bytes() {
+        let to_bytes = |strnum: &str| IssuedAmount{strnum: strnum.to_string()}.to_bytes();
+        assert_eq!(to_bytes(".5").unwrap(), to_bytes("0.5").unwrap());
+        assert_eq!(to_bytes("-.5").unwrap(), to_bytes("-0.5").unwrap());
+        assert_eq!(to_bytes(".5e1").unwrap(), to_bytes("5").unwrap());
+        assert_eq!(to_bytes("."), None);
+    }
+
+    #[test]
+    fn test_issued_amount_integer_to_bytes() {
+        let to_hex = |strnum: &str| IssuedAmount{strnum: strnum.to_string()}.to_bytes().map(hex::encode);
+        // 1 digit
+        assert_eq!(to_hex("1").unwrap(), "d4838d7ea4c68000");
+        assert_eq!(to_hex("-7").unwrap(), "9498de76816d8000");
+        // 10 digits
+        assert_eq!(to_hex("1234567890").unwrap(), "d6c462d53c88d880");
+        // 17 digits with 1 significant digit
+        assert_eq!(to_hex("10000000000000000").unwrap(), "d8838d7ea4c68000");
+        // 17 digits with 16 significant digits
+        assert_eq!(to_hex("12345678901234560").unwrap(), "d88462d53c8abac0");
+        // 17 significant digits are rounded at the 16th digit
+        assert_eq!(to_hex("12345678901234567").unwrap(), to_hex("12345678901234570").unwrap());
+        assert_eq!(to_hex("12345678901234564").unwrap(), to_hex("12345678901234560").unwrap());
+        assert_eq!(to_hex("99999999999999999").unwrap(), to_hex("100000000000000000").unwrap());
+    }
+
+    #[test]
+    fn test_issued_amount_range_to_bytes() {
+        let to_hex = |strnum: &str| IssuedAmount{strnum: strnum.to_string()}.to_bytes().map(hex::encode_upper);
+        // the largest and the smallest values, beyond the range of `Decimal`
+        assert_eq!(to_hex("9999999999999999e80").unwrap(), "EC6386F26FC0FFFF");
+        assert_eq!(to_hex("-9999999999999999e80").unwrap(), "AC6386F26FC0FFFF");
+        assert_eq!(to_hex("1e-81").unwrap(), "C0438D7EA4C68000");
+        assert_eq!(to_hex("0.000000000000000000000000000000000000000000000000000000000000000000000000000000001").unwrap(), "C0438D7EA4C68000");
+        // overflow and underflow
+        assert_eq!(to_hex("1e97"), None);
+        assert_eq!(to_hex("99999999999999995e80"), None);
+        assert_eq!(to_hex("1e-90"), None);
+        assert_eq!(to_hex("1e-2147483648"), None);
+        assert_eq!(to_hex("0e-200").unwrap(), "8000000000000000");
+        // round half up
+        assert_eq!(to_hex("1.0000000000000005").unwrap(), to_hex("1.000000000000001").unwrap());
+        assert_eq!(to_hex("1.00000000000000049").unwrap(), to_hex("1").unwrap());
+        assert_eq!(to_hex("-1.0000000000000005").unwrap(), to_hex("-1.000000000000001").unwrap());
+        assert_eq!(to_hex("1e"), None);
+        assert_eq!(to_hex("1.2.3"), None);
+        assert_eq!(to_hex("-"), None);
+    }
+
+    #[test]
+    fn test_issued_amount_round_trip_generated() {
+        let mut rng = XorShift::new();
+        let mut next = |bound: u64| rng.next_u64() % bound;
+        let to_integer = |digits: &[u8]| digits.iter().fold(0u128, |integer, digit| integer * 10 + u128::from(*digit));
+        for _ in 0..10000 {
+            // up to 19 significant digits, so that the ones beyond the 16th are rounded
+            let len = 1 + next(19) as usize;
+            let mut digits: Vec<u8> = (0..len).map(|_| next(10) as u8).collect();
+            digits[0] = 1 + next(9) as u8;
+            // the exponent of the 16 digits mantissa is kept in -96..=79, so that rounding up never overflows
+            let exp = next(176) as i32 - 96 - (len as i32 - 16);
+            let point = next(len as u64 + 1) as usize;
+            let is_negative = next(2) == 1;
+            let text: String = digits.iter().map(|digit| char::from(b'0' + digit)).collect();
+            let strnum = format!("{}{}.{}e{}", if is_negative { "-" } else { "" }, &text[..point], &text[point..], exp + (len - point) as i32);
+
+            let bytes = IssuedAmount{strnum: strnum.clone()}.to_bytes().unwrap_or_else(|| panic!("failed to serialize {}", strnum));
+            let decoded = IssuedAmount::from_bytes(&bytes).unwrap();
+            assert_eq!(IssuedAmount{strnum: decoded.strnum.clone()}.to_bytes().unwrap(), bytes, "{} decoded to {}", strnum, decoded.strnum);
+
+            // the expected value, rounded half away from zero at the 16th digit
+            let (mut expected, mut expected_exp) = (to_integer(&digits), exp);
+            if len > 16 {
+                let unit = 10u128.pow(len as u32 - 16);
+                expected = (expected + unit / 2) / unit;
+                expected_exp += len as i32 - 16;
+            }
+            let (decoded_negative, decoded_digits, decoded_exp) = parse_scientific(&decoded.strnum).unwrap();
+            let decoded_integer = to_integer(&decoded_digits);
+            let min_exp = expected_exp.min(decoded_exp);
+            assert_eq!(decoded_negative, is_negative, "{} decoded to {}", strnum, decoded.strnum);
+            assert_eq!(
+                expected * 10u128.pow((expected_exp - min_exp) as u32),
+                decoded_integer * 10u128.pow((decoded_exp - min_exp) as u32),
+                "{} decoded to {}", strnum, decoded.strnum,
+            );
+        }
+    }
 }
@@ -2,14 +2,53 @@
 
 use serde_json::Value;
 use hex::FromHex;
-use crate::definition_fields::SerializeField;
-use super::account::vl_encode;
+use bytes::BufMut;
+use crate::definition_fields::{FieldWriter, SerializeField};
+use super::account::write_vl_prefix;
 use alloc::vec::Vec;
 
+/// The length of a `SigningPubKey` in bytes, for both key types.
+const PUBLIC_KEY_LENGTH: usize = 33;
+
 pub struct Blob{
   pub data: Value
 }
 
+/// The signing algorithm of a public key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyType {
+  Ed25519,
+  Secp256k1,
+}
+
+/// Determine the key type of a `SigningPubKey` hex string, so the matching algorithm can be chosen to verify signatures.
+/// `Ed25519` keys are prefixed by `ED`, compressed `Secp256k1` keys by `02` or `03`.
+///
+/// # Example
+///
+///```
+///use rippled_binary_codec::types::blob::{key_type, KeyType};
+///
+///fn key_type_example(){
+///  let key_type = key_type("03EE83BB432547885C219634A1BC407A9DB0474145D69737D09CCDC63E1DEE7FE3");
+///  assert_eq!(key_type, Some(KeyType::Secp256k1));
+///}
+///```
+///
+/// # Errors
+///  If the input is not a 33 bytes hex string with a known prefix, `None` will be returned.
+pub fn key_type(pubkey: &str) -> Option<KeyType> {
+  let bytes = Vec::from_hex(pubkey).ok()?;
+  if bytes.len() != PUBLIC_KEY_LENGTH {
+    return None;
+  }
+  match bytes[0] {
+    0xED => Some(KeyType::Ed25519),
+    0x02 | 0x03 => Some(KeyType::Secp256k1),
+    _ => None,
+  }
+}
+
 impl SerializeField for Blob {
   /// Serialize an `Blob` field type. `None` will be returned if the serialization failed.
   ///
@@ -29,11 +68,27 @@ impl SerializeField for Blob {
   /// # Errors
   ///  If the field is failed to serialize, `None` will be returned.
   fn to_bytes(&self) -> Option<Vec<u8>>{
-    let input = self.data.as_str()?;
-    if let Ok(input) = Vec::from_hex(input){
-      return vl_encode(input);  
+    let mut buf = Vec::new();
+    Blob::write(&self.data, &mut FieldWriter::new(&mut buf))?;
+    Some(buf)
+  }
+}
+
+impl Blob {
+  /// Write a hex string with its length prefix into `out`, decoding it in chunks on the stack.
+  pub(crate) fn write<B: BufMut>(data: &Value, out: &mut FieldWriter<B>) -> Option<()> {
+    let input = data.as_str()?;
+    if input.len() % 2 != 0 || !input.bytes().all(|c| c.is_ascii_hexdigit()) {
+      return None;
     }
-    return None;
+    write_vl_prefix(input.len() / 2, out)?;
+    let mut decoded = [0u8; 64];
+    for chunk in input.as_bytes().chunks(2 * decoded.len()) {
+      let decoded = &mut decoded[..chunk.len() / 2];
+      hex::decode_to_slice(chunk, decoded).ok()?;
+      out.put_slice(decoded)?;
+    }
+    Some(())
   }
 }
 
@@ -56,4 +111,15 @@ mod tests {
     let expected2 =  b"F0D\x02 \x147YC|\x04\xf7\xb6\x1f\x01%c\xaf\xe9\r\x8d\xaf\xc4n\x86\x03^\x1d\x96Z\x9c\xed(,\x97\xd4\xce\x02 L\xfd$\x1e\x86\xf1~\x01\x12\x98\xfc\x1a9\xb63\x86\xc7C\x06\xa5\xde\x04~!;\x0f)\xef\xa4W\x1c,";
     assert_eq!(output2.unwrap(), expected2);
   }
+
+  #[test]
+  fn test_key_type() {
+    assert_eq!(key_type("ED5F5AC8B98974A3CA843326D9B88CEBD0560177B973EE0B149F782CFAA06DC66A"), Some(KeyType::Ed25519));
+    assert_eq!(key_type("03EE83BB432547885C219634A1BC407A9DB0474145D69737D09CCDC63E1DEE7FE3"), Some(KeyType::Secp256k1));
+    assert_eq!(key_type("02B87CEB1507849B6473773155827C0B8C15CB311C6876FBD7FAB95F06D3E18E39"), Some(KeyType::Secp256k1));
+    // uncompressed secp256k1 keys are not used by XRPL accounts
+    assert_eq!(key_type("04EE83BB432547885C219634A1BC407A9DB0474145D69737D09CCDC63E1DEE7FE3"), None);
+    assert_eq!(key_type("03EE83BB"), None);
+    assert_eq!(key_type(""), None);
+  }
 }
@@ -0,0 +1,102 @@
+//! Helpers to build the PREIMAGE-SHA-256 [crypto-conditions](https://datatracker.ietf.org/doc/html/draft-thomas-crypto-conditions-04)
+//! of an escrow, the `Condition` of `EscrowCreate` and the `Fulfillment` of `EscrowFinish`, which are serialized as `Blob` fields.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use cryptoxide::hashing;
+
+/// Build the PREIMAGE-SHA-256 condition of `preimage` in hex, for the `Condition` field of `EscrowCreate`:
+/// the SHA-256 fingerprint of the preimage and the cost, which is the length of the preimage.
+///
+/// # Example
+///
+///```
+///use rippled_binary_codec::types::condition::preimage_sha256_condition;
+///
+///fn preimage_sha256_condition_example(){
+///  let condition = preimage_sha256_condition(b"");
+///  assert_eq!(condition, "A0258020E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855810100");
+///}
+///```
+pub fn preimage_sha256_condition(preimage: &[u8]) -> String {
+  let mut fields = der_field(0x80, &hashing::sha256(preimage));
+  fields.extend_from_slice(&der_field(0x81, &der_unsigned(preimage.len())));
+  hex::encode_upper(der_field(0xA0, &fields))
+}
+
+/// Build the PREIMAGE-SHA-256 fulfillment of `preimage` in hex, for the `Fulfillment` field of `EscrowFinish`,
+/// which fulfills the condition built by [`preimage_sha256_condition`] of the same preimage.
+///
+/// # Example
+///
+///```
+///use rippled_binary_codec::types::condition::preimage_sha256_fulfillment;
+///
+///fn preimage_sha256_fulfillment_example(){
+///  let fulfillment = preimage_sha256_fulfillment(b"");
+///  assert_eq!(fulfillment, "A0028000");
+///}
+///```
+pub fn preimage_sha256_fulfillment(preimage: &[u8]) -> String {
+  hex::encode_upper(der_field(0xA0, &der_field(0x80, preimage)))
+}
+
+/// Frame `content` with a DER tag and length.
+fn der_field(tag: u8, content: &[u8]) -> Vec<u8> {
+  let mut field = vec![tag];
+  if content.len() < 0x80 {
+    field.push(content.len() as u8);
+  } else {
+    let len = der_unsigned(content.len());
+    let len = len.strip_prefix(&[0]).unwrap_or(&len);
+    field.push(0x80 | len.len() as u8);
+    field.extend_from_slice(len);
+  }
+  field.extend_from_slice(content);
+  field
+}
+
+/// Encode an unsigned integer in the fewest bytes of a DER integer, with a leading zero if its top bit is set.
+fn der_unsigned(value: usize) -> Vec<u8> {
+  let bytes = (value as u64).to_be_bytes();
+  let start = bytes.iter().position(|byte| *byte != 0).unwrap_or(bytes.len() - 1);
+  let mut encoded = Vec::with_capacity(9);
+  if bytes[start] & 0x80 != 0 {
+    encoded.push(0);
+  }
+  encoded.extend_from_slice(&bytes[start..]);
+  encoded
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use alloc::string::ToString;
+  use serde_json::json;
+  use crate::definition_fields::DefinitionFields;
+
+  #[test]
+  fn test_preimage_sha256() {
+    // the test vectors of an empty preimage in the crypto-conditions draft
+    assert_eq!(preimage_sha256_condition(b""), "A0258020E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855810100");
+    assert_eq!(preimage_sha256_fulfillment(b""), "A0028000");
+
+    let preimage = [0x33u8; 32];
+    let condition = preimage_sha256_condition(&preimage);
+    assert_eq!(&condition[..8], "A0258020");
+    assert_eq!(&condition[72..], "810120");
+    assert_eq!(preimage_sha256_fulfillment(&preimage), format!("A0228020{}", "33".repeat(32)));
+
+    // the long form length of a preimage of 200 bytes, and a cost with its top bit set
+    let preimage = [0x33u8; 200];
+    assert_eq!(&preimage_sha256_fulfillment(&preimage)[..12], "A081CB8081C8");
+    assert_eq!(&preimage_sha256_condition(&preimage)[72..], "810200C8");
+
+    // serialized as `Blob` fields
+    let fields = DefinitionFields::new();
+    let bytes = fields.field_to_bytes("Fulfillment".to_string(), json!(preimage_sha256_fulfillment(b""))).unwrap();
+    assert_eq!(hex::encode_upper(bytes), "701004A0028000");
+    let bytes = fields.field_to_bytes("Condition".to_string(), json!(preimage_sha256_condition(b""))).unwrap();
+    assert_eq!(hex::encode_upper(&bytes[..4]), "701127A0");
+  }
+}
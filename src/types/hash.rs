@@ -1,10 +1,10 @@
-//! Methods to serialize `Hash128`, `Hash160`, `Hash256` type of fields to bytes.
+//! Methods to serialize `Hash128`, `Hash160`, `Hash192`, `Hash256` type of fields, and the fixed width `UInt96` to `UInt192` given in hex, to bytes.
 
+use bytes::BufMut;
 use serde_json::Value;
-use alloc::string::ToString;
 use alloc::vec::Vec;
 
-use crate::definition_fields::SerializeField;
+use crate::definition_fields::{FieldWriter, SerializeField};
 
 /// A structure that representing `Hash128`, `Hash160`, `HAsh256` type of field.
 pub struct Hash{
@@ -38,13 +38,19 @@ impl SerializeField for Hash{
   /// # Errors
   ///  If the field is failed to serialize, `None` will be returned.
   fn to_bytes(&self) -> Option<Vec<u8>>{
-    let input: &str = self.data.as_str()?;
-    let decoded = hex::decode(input.to_string()).ok()?;
-    let input_len: u8 = decoded.len() as u8;
-    if self.len == input_len{
-      return Some(decoded);
-    }
-    return None;
+    let mut buf = Vec::with_capacity(usize::from(self.len));
+    Hash::write(&self.data, usize::from(self.len), &mut FieldWriter::new(&mut buf))?;
+    Some(buf)
+  }
+}
+
+impl Hash {
+  /// Write a hex string of `len` bytes into `out`, decoding it on the stack.
+  pub(crate) fn write<B: BufMut>(data: &Value, len: usize, out: &mut FieldWriter<B>) -> Option<()> {
+    let mut decoded = [0u8; 32];
+    let decoded = decoded.get_mut(..len)?;
+    hex::decode_to_slice(data.as_str()?, decoded).ok()?;
+    out.put_slice(decoded)
   }
 }
 
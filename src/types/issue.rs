@@ -0,0 +1,127 @@
+//! A structure represents `Issue` type of field in ripple transaction, like `Asset` and `Asset2` of the AMM transactions.
+
+use core::convert::TryInto;
+use bytes::BufMut;
+use serde_json::{Value, json};
+use alloc::vec::Vec;
+use crate::definition_fields::{FieldWriter, SerializeField};
+use crate::ripple_address_codec::{decode_account_id, encode_account_id};
+use super::amount::{currency_code, currency_code_from_bytes};
+
+/// A structure represents `Issue` type of field, an asset without amount: `{"currency": "XRP"}` or `{"currency": ..., "issuer": ...}`.
+pub struct Issue {
+  pub data: Value
+}
+
+impl Issue {
+  /// Return the length of the serialized `Issue` at the start of `bytes`, 20 bytes for XRP or 40 bytes for an issued currency.
+  ///
+  /// `None` will be returned if `bytes` is too short to tell.
+  pub fn len_of(bytes: &[u8]) -> Option<usize> {
+    let currency = bytes.get(..20)?;
+    if currency.iter().all(|byte| *byte == 0) {
+      Some(20)
+    } else {
+      Some(40)
+    }
+  }
+
+  /// Deserialize an `Issue` field from bytes, the inverse of [`to_bytes()`][`Issue::to_bytes`].
+  ///
+  /// # Example
+  ///
+  ///```
+  ///use rippled_binary_codec::types::issue::Issue;
+  ///
+  ///fn issue_from_bytes_example(){
+  ///  let issue = Issue::from_bytes(&[0u8; 20]).unwrap();
+  ///  println!("issue: {}", issue); // {"currency":"XRP"}
+  ///}
+  ///```
+  ///
+  /// # Errors
+  ///  If `bytes` is not a 20 bytes XRP issue or a 40 bytes issued currency, `None` will be returned.
+  pub fn from_bytes(bytes: &[u8]) -> Option<Value> {
+    if bytes.len() != Issue::len_of(bytes)? {
+      return None;
+    }
+    let currency = currency_code_from_bytes(&bytes[..20])?;
+    if bytes.len() == 20 {
+      return Some(json!({"currency": currency}));
+    }
+    let issuer = encode_account_id(bytes[20..].try_into().ok()?);
+    Some(json!({"currency": currency, "issuer": issuer}))
+  }
+
+  /// Write an `Issue` into `out`, see [`to_bytes()`][`Issue::to_bytes`].
+  pub(crate) fn write<B: BufMut>(data: &Value, out: &mut FieldWriter<B>) -> Option<()> {
+    let obj = data.as_object()?;
+    let currency = obj.get("currency")?.as_str()?;
+    let code = currency_code(currency, true)?;
+    match (code == [0u8; 20], obj.get("issuer")) {
+      (true, None) => out.put_slice(&code),
+      (false, Some(issuer)) => {
+        let issuer = decode_account_id(issuer.as_str()?).ok()?;
+        out.put_slice(&code)?;
+        out.put_slice(&issuer)
+      },
+      _ => None,
+    }
+  }
+}
+
+impl SerializeField for Issue {
+  /// Serialize an `Issue`, the 20 bytes currency code, followed by the 20 bytes issuer `AccountID` if the currency is not XRP.
+  ///
+  /// # Example
+  ///
+  ///```
+  ///use rippled_binary_codec::types::issue::Issue;
+  ///use rippled_binary_codec::definition_fields::SerializeField;
+  ///use serde_json::json;
+  ///
+  ///fn issue_to_bytes_example(){
+  ///  let bytes = Issue{data: json!({"currency": "XRP"})}.to_bytes().unwrap();
+  ///  println!("serialized issue: {:?}", bytes); // [0; 20]
+  ///}
+  ///```
+  ///
+  /// # Errors
+  ///  If the currency is invalid, an XRP issue has an issuer, or an issued currency has none, `None` will be returned.
+  fn to_bytes(&self) -> Option<Vec<u8>> {
+    let mut buf = Vec::with_capacity(40);
+    Issue::write(&self.data, &mut FieldWriter::new(&mut buf))?;
+    Some(buf)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_issue_to_bytes() {
+    let xrp = json!({"currency": "XRP"});
+    let output = Issue{data: xrp.clone()}.to_bytes().unwrap();
+    assert_eq!(output, [0u8; 20]);
+    assert_eq!(Issue::from_bytes(&output).unwrap(), xrp);
+
+    let eth = json!({"currency": "ETH", "issuer": "rPDXxSZcuVL3ZWoyU82bcde3zwvmShkRyF"});
+    let output = Issue{data: eth.clone()}.to_bytes().unwrap();
+    assert_eq!(hex::encode_upper(&output), "0000000000000000000000004554480000000000F3B1997562FD742B54D4EBDEA1D6AEA3D4906B8F");
+    assert_eq!(Issue::from_bytes(&output).unwrap(), eth);
+
+    assert_eq!(Issue{data: json!({"currency": "XRP", "issuer": "rPDXxSZcuVL3ZWoyU82bcde3zwvmShkRyF"})}.to_bytes(), None);
+    assert_eq!(Issue{data: json!({"currency": "ETH"})}.to_bytes(), None);
+    assert_eq!(Issue{data: json!({"currency": "xrp"})}.to_bytes().unwrap(), [0u8; 20]);
+    assert_eq!(Issue{data: json!({"currency": "Xrp", "issuer": "rPDXxSZcuVL3ZWoyU82bcde3zwvmShkRyF"})}.to_bytes(), None);
+    assert_eq!(Issue::from_bytes(&output[..30]), None);
+  }
+
+  #[test]
+  fn test_issue_zero_hex_currency() {
+    let zero = "0000000000000000000000000000000000000000";
+    assert_eq!(Issue{data: json!({"currency": zero})}.to_bytes().unwrap(), [0u8; 20]);
+    assert_eq!(Issue{data: json!({"currency": zero, "issuer": "rPDXxSZcuVL3ZWoyU82bcde3zwvmShkRyF"})}.to_bytes(), None);
+  }
+}
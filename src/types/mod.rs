@@ -3,10 +3,14 @@
 pub mod account;
 pub mod definition;
 pub mod amount;
+pub mod issue;
+pub mod number;
 pub mod path_set;
 pub mod hash;
 pub mod blob;
+pub mod condition;
 pub mod starray;
 pub mod stobject;
 
-
+// the length prefix of the VL-encoded fields, used by `Blob`, `AccountID` and the deserializer
+pub use account::{vl_decode, vl_encode};
@@ -0,0 +1,86 @@
+//! A structure represents `Number` type of field in ripple transaction, a decimal of a 64-bit mantissa and a 32-bit exponent.
+
+use bytes::BufMut;
+use core::convert::TryFrom;
+use serde_json::Value;
+use alloc::vec::Vec;
+use crate::definition_fields::{FieldWriter, SerializeField};
+use super::amount::normalize_decimal;
+
+const MIN_EXP: i32 = -32768;
+const MAX_EXP: i32 = 32768;
+
+/// A structure represents `Number` type of field, given as a decimal string like `"1.5"` or `"-2e10"`.
+pub struct Number {
+  pub data: Value
+}
+
+impl SerializeField for Number {
+  /// Serialize a `Number`, the normalized mantissa as a big-endian `i64` followed by the exponent as a big-endian `i32`, 12 bytes in total.
+  ///
+  /// The mantissa is normalized to 16 digits the same as an issued amount, a value with more than 16 significant digits being
+  /// rounded half away from zero, but the exponent ranges from -32768 to 32768. Zero is serialized as the mantissa 0 and the
+  /// exponent `i32::MIN`, the same as rippled.
+  ///
+  /// # Example
+  ///
+  ///```
+  ///use rippled_binary_codec::types::number::Number;
+  ///use rippled_binary_codec::definition_fields::SerializeField;
+  ///use serde_json::json;
+  ///
+  ///fn number_to_bytes_example(){
+  ///  let bytes = Number{data: json!("1.5")}.to_bytes().unwrap();
+  ///  println!("serialized number: {:?}", bytes); // [0, 5, 84, 61, 247, 41, 192, 0, 255, 255, 255, 241]
+  ///}
+  ///```
+  ///
+  /// # Errors
+  ///  If the value is not a decimal string or its exponent is out of range after normalizing, `None` will be returned.
+  fn to_bytes(&self) -> Option<Vec<u8>> {
+    let mut buf = Vec::with_capacity(12);
+    Number::write(&self.data, &mut FieldWriter::new(&mut buf))?;
+    Some(buf)
+  }
+}
+
+impl Number {
+  /// Write a `Number` into `out`, see [`to_bytes()`][`Number::to_bytes`].
+  pub(crate) fn write<B: BufMut>(data: &Value, out: &mut FieldWriter<B>) -> Option<()> {
+    let (is_negative, mantissa, exp) = normalize_decimal(data.as_str()?)?;
+    if mantissa == 0 {
+      out.put_u64(0)?;
+      return out.put_u32(i32::MIN as u32);
+    }
+    if !(MIN_EXP..=MAX_EXP).contains(&exp) {
+      return None;
+    }
+    let mantissa = i64::try_from(mantissa).ok()?;
+    out.put_u64((if is_negative { -mantissa } else { mantissa }) as u64)?;
+    out.put_u32(exp as u32)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use serde_json::json;
+  use super::*;
+
+  #[test]
+  fn test_number_to_bytes() {
+    let to_hex = |value: &str| Number{data: json!(value)}.to_bytes().map(hex::encode_upper);
+    assert_eq!(to_hex("1.5").unwrap(), "0005543DF729C000FFFFFFF1");
+    assert_eq!(to_hex("-1.5").unwrap(), "FFFAABC208D64000FFFFFFF1");
+    assert_eq!(to_hex("1").unwrap(), "00038D7EA4C68000FFFFFFF1");
+    assert_eq!(to_hex("1e-3").unwrap(), to_hex("0.001").unwrap());
+    assert_eq!(to_hex("0").unwrap(), "000000000000000080000000");
+    assert_eq!(to_hex("1234567890123456789").unwrap(), "000462D53C8ABAC100000003");
+    assert_eq!(to_hex("1234567890123456449").unwrap(), to_hex("1234567890123456000").unwrap());
+    assert_eq!(to_hex("1e32783").unwrap(), "00038D7EA4C6800000008000");
+    assert_eq!(to_hex("1e-32753").unwrap(), "00038D7EA4C68000FFFF8000");
+    assert_eq!(to_hex("1e32784"), None);
+    assert_eq!(to_hex("1e-32754"), None);
+    assert_eq!(to_hex("abc"), None);
+    assert_eq!(Number{data: json!(1.5)}.to_bytes(), None);
+  }
+}
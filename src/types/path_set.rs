@@ -1,12 +1,18 @@
 //! A structure represents `PathSet` type of field in ripple transaction and methods to serializes them to bytes.
 
-use bytes::{BytesMut, BufMut};
+use bytes::BufMut;
 use serde_json::Value;
-use crate::definition_fields::SerializeField;
+use crate::definition_fields::{FieldWriter, SerializeField};
+use crate::errors::{Result, RippleBinaryCodecError::PathSetTooLarge};
 use alloc::vec::Vec;
-use super::amount::currency_code_to_bytes;
+use super::amount::currency_code;
 use crate::ripple_address_codec::decode_account_id;
 
+/// The maximum number of paths in a `PathSet` accepted by rippled.
+pub const MAX_PATHS: usize = 6;
+/// The maximum number of steps in a path accepted by rippled.
+pub const MAX_PATH_STEPS: usize = 8;
+
 /// A structure represents `PathSet` type of field.
 pub struct PathSet {
   pub data: Value
@@ -47,68 +53,107 @@ impl SerializeField for PathSet {
   /// # Errors
   ///  If the field is failed to serialize, `None` will be returned.
   fn to_bytes(&self) -> Option<Vec<u8>>{
-    if let Some(pathset) = self.data.as_array(){
-      let mut buf = BytesMut::with_capacity(0);
-      for i in 0..pathset.len(){
-          if let Some(path) = PathSet::path_as_bytes(pathset[i].clone()){
-            buf.extend_from_slice(&path);
-          }
-          if i+1 == pathset.len(){
-          // last path; add an end byte
-            buf.put_u8(0x00);
-          }else{
-          // add a path separator byte
-            buf.put_u8(0xff);
-          }
-        }
-        return Some(buf.freeze().to_vec());
+    let mut buf = Vec::new();
+    PathSet::write(&self.data, &mut FieldWriter::new(&mut buf))?;
+    Some(buf)
+  }
+}
+
+/// Compute the length of the serialized `PathSet` without serializing it, for estimating the fee of a transaction.
+///
+/// The length is the same as [`PathSet::to_bytes`], but the values of the steps are not checked.
+///
+/// # Example
+///
+///```
+///use rippled_binary_codec::types::path_set::pathset_serialized_len;
+///use serde_json::json;
+///
+///fn pathset_serialized_len_example(){
+///  let paths = json!([[{"account": "rPDXxSZcuVL3ZWoyU82bcde3zwvmShkRyF"}]]);
+///  println!("length: {:?}", pathset_serialized_len(&paths)); // Some(22)
+///}
+///```
+///
+/// # Errors
+///  If `paths` is not an array of arrays, `None` will be returned.
+pub fn pathset_serialized_len(paths: &Value) -> Option<usize> {
+  let mut len = 0;
+  for path in paths.as_array()? {
+    for step in path.as_array()?.iter().filter_map(Value::as_object) {
+      let components = ["account", "currency", "issuer"].iter().filter(|key| step.contains_key(**key)).count();
+      if components > 0 {
+        // the type byte and a 20 bytes value for each component
+        len += 1 + 20 * components;
+      }
     }
-    return None;
+    // a path separator or the end byte
+    len += 1;
   }
+  Some(len)
 }
 
 impl PathSet {
-  /// representing one member of a pathset as a bytes object
-  fn path_as_bytes( path: Value) -> Option<Vec<u8>> {
-    if let Some(path) = path.as_array(){
-      let mut path_contents = BytesMut::with_capacity(0);
-      for step in path {
-        let mut step_data = BytesMut::with_capacity(0);
-        if let Some(obj) = step.as_object(){
-            let account_key = "account";
-            let currency_key ="currency";
-            let issuer_key = "issuser";
-            if obj.contains_key::<str>(&account_key){
-              if let Some(account_value) = obj.get::<str>(&account_key) {
-                let account = account_value.as_str()?;
-                if let Ok(data) = decode_account_id(account){
-                  step_data.put_u8(0x01);
-                  step_data.extend_from_slice(&data);
-                }
-              }
-            }else if obj.contains_key::<str>(&currency_key){
-              if let Some(currency_value) = obj.get::<str>(&currency_key) {
-                let currency = currency_value.as_str()?;
-                if let Some(data) = currency_code_to_bytes(currency, true){
-                  step_data.put_u8(0x10);
-                  step_data.extend_from_slice(&data);
-                }
-              }
-            }else if obj.contains_key::<str>(&issuer_key){
-              if let Some(issuer_value) = obj.get::<str>(&issuer_key) {
-                let issuer = issuer_value.as_str()?;
-                if let Ok(data) = decode_account_id(issuer){
-                  step_data.put_u8(0x20);
-                  step_data.extend_from_slice(&data);
-                }
-              }
-            }
+  /// Check that the `PathSet` has at most [`MAX_PATHS`] paths and each path has at most [`MAX_PATH_STEPS`] steps,
+  /// rippled rejects a transaction whose `PathSet` exceeds these limits.
+  ///
+  /// # Errors
+  ///  If the limits are exceeded, [`PathSetTooLarge`][`crate::errors::RippleBinaryCodecError::PathSetTooLarge`] will be returned
+  ///  with the number of paths and the number of steps of the longest path.
+  pub fn validate(&self) -> Result<()> {
+    if let Some(pathset) = self.data.as_array() {
+      let paths = pathset.len();
+      let steps = pathset.iter().filter_map(|path| path.as_array()).map(|path| path.len()).max().unwrap_or(0);
+      if paths > MAX_PATHS || steps > MAX_PATH_STEPS {
+        return Err(PathSetTooLarge { paths, steps });
+      }
+    }
+    Ok(())
+  }
+
+  /// Write a `PathSet` into `out`, see [`to_bytes()`][`PathSet::to_bytes`].
+  pub(crate) fn write<B: BufMut>(data: &Value, out: &mut FieldWriter<B>) -> Option<()> {
+    let pathset = data.as_array()?;
+    for (i, path) in pathset.iter().enumerate() {
+      PathSet::write_path(path, out)?;
+      if i+1 == pathset.len(){
+        // last path; add an end byte
+        out.put_u8(0x00)?;
+      }else{
+        // add a path separator byte
+        out.put_u8(0xff)?;
+      }
+    }
+    Some(())
+  }
+
+  /// Write one member of a pathset.
+  fn write_path<B: BufMut>(path: &Value, out: &mut FieldWriter<B>) -> Option<()> {
+    for step in path.as_array()? {
+      if let Some(obj) = step.as_object(){
+        // the type byte is the OR of the flags of the present components, which follow it in the same order
+        let account = match obj.get("account") {
+          Some(account) => Some(decode_account_id(account.as_str()?).ok()?),
+          None => None,
+        };
+        let currency = match obj.get("currency") {
+          Some(currency) => Some(currency_code(currency.as_str()?, true)?),
+          None => None,
+        };
+        let issuer = match obj.get("issuer") {
+          Some(issuer) => Some(decode_account_id(issuer.as_str()?).ok()?),
+          None => None,
+        };
+        let step_type = account.map_or(0, |_| 0x01) | currency.map_or(0, |_| 0x10) | issuer.map_or(0, |_| 0x20);
+        if step_type != 0 {
+          out.put_u8(step_type)?;
+        }
+        for component in [account, currency, issuer].iter().flatten() {
+          out.put_slice(component)?;
         }
-        path_contents.extend_from_slice(&step_data);
       }
-      return Some(path_contents.to_vec());
     }
-    return None;
+    Some(())
   }
 }
 
@@ -190,4 +235,66 @@ mod tests {
       let expected =  "01F3B1997562FD742B54D4EBDEA1D6AEA3D4906B8F100000000000000000000000000000000000000000FF014B4E9C06F24296074F7BC48F92A97916C6DC5EA901DD39C650A96EDA48334E70CC4A85B8B2E8502CD310000000000000000000000000000000000000000000";
       assert_eq!(hex::encode(output.clone()).to_uppercase(), expected);
     }
+
+    #[test]
+    fn test_pathset_serialized_len() {
+      let input = json!([
+        [
+          {"account": "rPDXxSZcuVL3ZWoyU82bcde3zwvmShkRyF"},
+          {"currency": "XRP"}
+        ],
+        [
+          {"account": "rf1BiGeXwwQoi8Z2ueFYTEXSwuJYfV2Jpn"},
+          {"currency": "USD", "issuer": "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B"},
+          {"currency": "XRP"}
+        ]
+      ]);
+      let output = PathSet{data: input.clone()}.to_bytes().unwrap();
+      assert_eq!(pathset_serialized_len(&input), Some(output.len()));
+      assert_eq!(pathset_serialized_len(&json!([])), Some(0));
+      assert_eq!(pathset_serialized_len(&json!([{"currency": "XRP"}])), None);
+    }
+
+    #[test]
+    fn test_pathset_to_bytes_combined_step() {
+      let input = json!([
+        [
+          {
+            "currency": "USD",
+            "issuer": "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B",
+            "type": 48,
+            "type_hex": "0000000000000030"
+          },
+          {
+            "account": "rPDXxSZcuVL3ZWoyU82bcde3zwvmShkRyF",
+            "currency": "XRP"
+          }
+        ]
+      ]);
+      let output = PathSet{data: input}.to_bytes().unwrap();
+      let expected = concat!(
+        "3000000000000000000000000055534400000000000A20B3C85F482532A9578DBB3950B85CA06594D1",
+        "11F3B1997562FD742B54D4EBDEA1D6AEA3D4906B8F0000000000000000000000000000000000000000",
+        "00"
+      );
+      assert_eq!(hex::encode(output).to_uppercase(), expected);
+
+      let invalid_issuer = json!([[{"currency": "USD", "issuer": "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59C"}]]);
+      assert_eq!(PathSet{data: invalid_issuer}.to_bytes(), None);
+    }
+
+    #[test]
+    fn test_pathset_validate() {
+      let step = json!({"currency": "XRP"});
+      let path = |steps: usize| Value::Array(vec![step.clone(); steps]);
+
+      let input = Value::Array(vec![path(MAX_PATH_STEPS); MAX_PATHS]);
+      assert_eq!(PathSet{data: input}.validate(), Ok(()));
+
+      let input = Value::Array(vec![path(1); MAX_PATHS + 1]);
+      assert_eq!(PathSet{data: input}.validate(), Err(PathSetTooLarge { paths: 7, steps: 1 }));
+
+      let input = Value::Array(vec![path(2), path(MAX_PATH_STEPS + 1)]);
+      assert_eq!(PathSet{data: input}.validate(), Err(PathSetTooLarge { paths: 2, steps: 9 }));
+    }
 }
@@ -1,11 +1,11 @@
 //! A structure represents `STArray` type of field.
 
-use serde_json::Value;
-use crate::definition_fields::{DefinitionFields, SerializeField};
-use bytes::BytesMut;
+use serde_json::{Map, Value};
+use crate::definition_fields::{DefinitionFields, FieldWriter, SerializeField};
 use alloc::vec::Vec;
-use alloc::string::{ToString, String};
+use alloc::string::ToString;
 use crate::alloc::borrow::ToOwned;
+use crate::ripple_address_codec::decode_account_id;
 
 /// A structure represents `STArray` type of field.
 pub struct STArray<'a> {
@@ -13,6 +13,62 @@ pub struct STArray<'a> {
   pub definition_fields: &'a DefinitionFields
 }
 
+/// Sort the entries of a `Signers` array by the numeric value of their `Account`, the order rippled requires for a multi-signed transaction.
+///
+/// # Example
+///
+///```
+///use rippled_binary_codec::types::starray::sort_signers;
+///use serde_json::json;
+///
+///fn sort_signers_example(){
+///  let signers = json!([
+///    {"Signer": {"Account": "rQGu1Zh1rBNt5eCDfuvR1zvV9MT8CPgwLk"}},
+///    {"Signer": {"Account": "rGUmkyLbvqGF3hwX4qwGHdrzLdY2Qpskum"}}
+///  ]);
+///  let sorted = sort_signers(&signers).unwrap();
+///  assert_eq!(sorted[0]["Signer"]["Account"], "rGUmkyLbvqGF3hwX4qwGHdrzLdY2Qpskum");
+///}
+///```
+///
+/// # Errors
+///  If an entry is not a `Signer` with a valid `Account`, `None` will be returned.
+pub fn sort_signers(signers: &Value) -> Option<Value> {
+  let mut keyed = Vec::new();
+  for signer in signers.as_array()? {
+    let account_id = decode_account_id(signer.get("Signer")?.get("Account")?.as_str()?).ok()?;
+    keyed.push((account_id, signer.to_owned()));
+  }
+  keyed.sort_by_key(|(account_id, _)| *account_id);
+  Some(Value::Array(keyed.into_iter().map(|(_, signer)| signer).collect()))
+}
+
+/// Make a `Memo` entry of a `Memos` array, hex-encoding the UTF-8 bytes of each present field, since `MemoType`, `MemoData`
+/// and `MemoFormat` are `Blob` fields, which are serialized from hex.
+///
+/// # Example
+///
+///```
+///use rippled_binary_codec::types::starray::make_memo;
+///use serde_json::json;
+///
+///fn make_memo_example(){
+///  let memo = make_memo(Some("text/plain"), Some("rent"), None);
+///  assert_eq!(memo, json!({"Memo": {"MemoType": "746578742F706C61696E", "MemoData": "72656E74"}}));
+///}
+///```
+pub fn make_memo(memo_type: Option<&str>, memo_data: Option<&str>, memo_format: Option<&str>) -> Value {
+  let mut memo = Map::new();
+  for (field_name, field_val) in [("MemoType", memo_type), ("MemoData", memo_data), ("MemoFormat", memo_format)] {
+    if let Some(field_val) = field_val {
+      memo.insert(field_name.to_string(), Value::String(hex::encode_upper(field_val)));
+    }
+  }
+  let mut wrapper = Map::new();
+  wrapper.insert("Memo".to_string(), Value::Object(memo));
+  Value::Object(wrapper)
+}
+
 impl SerializeField for STArray<'_> {
   /// Serialize an `STArray` field type. `None` will be returned if the serialization failed.
   ///
@@ -41,23 +97,9 @@ impl SerializeField for STArray<'_> {
   /// # Errors
   ///  If the field is failed to serialize, `None` will be returned.
   fn to_bytes(&self) -> Option<Vec<u8>>{
-    if let Some(data) = self.data.as_array(){
-      let mut buf = BytesMut::with_capacity(0);
-      for el in data.into_iter(){
-        if let Some(inner) = el.as_object(){
-          let wrapper_keys: Vec<String> = inner.keys().cloned().collect();
-          let fields = self.definition_fields.field_to_bytes(wrapper_keys[0].to_owned(),el.to_owned());
-            if let Some(fields) = fields {
-              buf.extend_from_slice(&fields);
-            }
-        }
-      }
-      if let Some(array_end_marker) = self.definition_fields.get_field_id("ArrayEndMarker".to_string()){
-        buf.extend_from_slice(&array_end_marker);
-      }
-      return Some(buf.to_vec());
-    }
-    return None;
+    let mut buf = Vec::new();
+    self.definition_fields.write_array(&self.data, &mut FieldWriter::new(&mut buf))?;
+    Some(buf)
   }
 }
 
@@ -104,4 +146,32 @@ mod tests {
     let expected3=b"\xea|\x1fhttp://example.com/memo/generic}\x04rent\xe1\xf1";
     assert_eq!(output3.unwrap(), expected3);
   }
+
+  #[test]
+  fn test_sort_signers(){
+    let signers = json!([
+      {"Signer": {"Account": "rQGu1Zh1rBNt5eCDfuvR1zvV9MT8CPgwLk", "SigningPubKey": ""}},
+      {"Signer": {"Account": "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys", "SigningPubKey": ""}},
+      {"Signer": {"Account": "rGUmkyLbvqGF3hwX4qwGHdrzLdY2Qpskum", "SigningPubKey": ""}}
+    ]);
+    let expected = json!([
+      {"Signer": {"Account": "rGUmkyLbvqGF3hwX4qwGHdrzLdY2Qpskum", "SigningPubKey": ""}},
+      {"Signer": {"Account": "rMBzp8CgpE441cp5PVyA9rpVV7oT8hP3ys", "SigningPubKey": ""}},
+      {"Signer": {"Account": "rQGu1Zh1rBNt5eCDfuvR1zvV9MT8CPgwLk", "SigningPubKey": ""}}
+    ]);
+    assert_eq!(sort_signers(&signers), Some(expected));
+    assert_eq!(sort_signers(&json!([{"Signer": {"SigningPubKey": ""}}])), None);
+    assert_eq!(sort_signers(&json!([{"Memo": {"MemoData": "72656e74"}}])), None);
+  }
+
+  #[test]
+  fn test_make_memo(){
+    assert_eq!(make_memo(None, Some("rt1.1.1"), None), json!({"Memo": {"MemoData": "7274312E312E31"}}));
+    assert_eq!(make_memo(None, None, None), json!({"Memo": {}}));
+    let memo = make_memo(Some("http://example.com/memo/generic"), Some("rent"), Some("text/plain"));
+    assert_eq!(memo["Memo"]["MemoFormat"], "746578742F706C61696E");
+    let output = STArray{data: json!([memo]), definition_fields: &DefinitionFields::new()}.to_bytes();
+    let expected = b"\xea|\x1fhttp://example.com/memo/generic}\x04rent~\x0atext/plain\xe1\xf1";
+    assert_eq!(output.unwrap(), expected);
+  }
 }
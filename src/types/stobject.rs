@@ -1,10 +1,8 @@
 //! A structure represents `STObject` type of field.
 
 use serde_json::Value;
-use bytes::BytesMut;
-use crate::definition_fields::{DefinitionFields, SerializeField};
+use crate::definition_fields::{DefinitionFields, FieldWriter, SerializeField};
 use alloc::vec::Vec;
-use alloc::string::{String,ToString};
 
 /// A structure represents `STObject` type of field.
 pub struct STObject<'a>{
@@ -37,27 +35,9 @@ impl SerializeField for STObject<'_>{
   /// # Errors
   ///  If the field is failed to serialize, `None` will be returned. 
   fn to_bytes(&self) -> Option<Vec<u8>>{
-    if let Some(data) = self.data.as_object(){
-      let wrapper_keys: Vec<String> = data.keys().cloned().collect();
-      let inner_object = data.get(&wrapper_keys[0])?;
-      if let Some(inner_obj) = inner_object.as_object(){
-        let inner_keys: Vec<String> = inner_obj.keys().cloned().collect();
-        let child_order = self.definition_fields.ordering_fields(inner_keys);
-        let mut buf = BytesMut::with_capacity(0);
-        for field_name in child_order {
-          let is_serialized = self.definition_fields.get_definition_field(field_name.clone())?.is_serialized;
-          if is_serialized {
-            let field_val: Value =  self.definition_fields.get_field_by_name(inner_obj, field_name.as_str())?;
-            let field_bytes : Vec<u8> = self.definition_fields.field_to_bytes(field_name, field_val)?;
-            buf.extend_from_slice(&field_bytes);
-          }
-        }
-        let end_mark = self.definition_fields.get_field_id("ObjectEndMarker".to_string())?;
-        buf.extend_from_slice(&end_mark);
-        return Some(buf.to_vec())
-      }
-    }
-    return None;
+    let mut buf = Vec::new();
+    self.definition_fields.write_object(&self.data, &mut FieldWriter::new(&mut buf))?;
+    Some(buf)
   }
 }
 
@@ -98,4 +78,24 @@ mod tests {
       let expected3=  b"\x13\x00\x01\x81\x14y\x08\xa7\xf0\xed\xd4\x8e\xa8\x96\xc3X\n9\x9f\x0e\xe7\x86\x11\xc8\xe3\xe1";
       assert_eq!(output3.unwrap(), expected3);
   }
+
+  #[test]
+  fn test_signer_object_to_bytes() {
+      // the inner fields are ordered as `SigningPubKey`, `TxnSignature` (Blob) and `Account` (AccountID)
+      let input = json!({
+        "Signer": {
+            "Account": "rGUmkyLbvqGF3hwX4qwGHdrzLdY2Qpskum",
+            "TxnSignature": "30440220143759437C04F7B61F012563AFE90D8DAFC46E86035E1D965A9CED282C97D4CE02204CFD241E86F17E011298FC1A39B63386C74306A5DE047E213B0F29EFA4571C2C",
+            "SigningPubKey": "03F5C5BB1D19EC710D3D7FAD199AF10CF8BC1D11348E5B3765C0B0B9C0BEC32879"
+        }
+      });
+      let output = STObject{data: input, definition_fields: &DefinitionFields::new()}.to_bytes().unwrap();
+      let expected = concat!(
+        "732103F5C5BB1D19EC710D3D7FAD199AF10CF8BC1D11348E5B3765C0B0B9C0BEC32879",
+        "744630440220143759437C04F7B61F012563AFE90D8DAFC46E86035E1D965A9CED282C97D4CE02204CFD241E86F17E011298FC1A39B63386C74306A5DE047E213B0F29EFA4571C2C",
+        "8114A6C3D314FB5418627AB22D9DDF6C18AED5F6CA89",
+        "E1"
+      );
+      assert_eq!(hex::encode_upper(output), expected);
+  }
 }
@@ -0,0 +1,228 @@
+//! Checks on a transaction JSON which catch the mistakes making rippled reject the serialized transaction.
+
+use serde_json::{Map, Value};
+use alloc::collections::BTreeSet;
+use alloc::string::{String, ToString};
+use crate::definition_fields::DefinitionFields;
+use crate::errors::{Result, RippleBinaryCodecError::{ConflictingFields, DecodeError, DisallowedField, InvalidFieldValue, InvalidPublicKey}};
+use crate::types::blob::key_type;
+use crate::types::path_set::PathSet;
+
+/// The fields required by all transactions, checked by [`validate_required`][`DefinitionFields::validate_required`].
+pub const COMMON_REQUIRED_FIELDS: [&str; 4] = ["TransactionType", "Account", "Fee", "Sequence"];
+
+/// The fields required by each transaction type besides [`COMMON_REQUIRED_FIELDS`], without which rippled rejects the
+/// transaction as malformed, checked by [`validate_required`][`DefinitionFields::validate_required`].
+pub const REQUIRED_FIELDS: [(&str, &[&str]); 25] = [
+  ("Payment", &["Destination", "Amount"]),
+  ("OfferCreate", &["TakerGets", "TakerPays"]),
+  ("OfferCancel", &["OfferSequence"]),
+  // optional in the format, but the preflight rejects a missing limit as `temBAD_LIMIT`
+  ("TrustSet", &["LimitAmount"]),
+  ("AccountDelete", &["Destination"]),
+  ("EscrowCreate", &["Destination", "Amount"]),
+  ("EscrowFinish", &["Owner", "OfferSequence"]),
+  ("EscrowCancel", &["Owner", "OfferSequence"]),
+  ("PaymentChannelCreate", &["Amount", "Destination", "SettleDelay", "PublicKey"]),
+  ("PaymentChannelFund", &["Channel", "Amount"]),
+  ("PaymentChannelClaim", &["Channel"]),
+  ("SignerListSet", &["SignerQuorum"]),
+  ("CheckCreate", &["Destination", "SendMax"]),
+  ("CheckCash", &["CheckID"]),
+  ("CheckCancel", &["CheckID"]),
+  ("TicketCreate", &["TicketCount"]),
+  ("NFTokenMint", &["NFTokenTaxon"]),
+  ("NFTokenBurn", &["NFTokenID"]),
+  ("NFTokenCreateOffer", &["NFTokenID", "Amount"]),
+  ("AMMCreate", &["Amount", "Amount2", "TradingFee"]),
+  ("AMMDeposit", &["Asset", "Asset2"]),
+  ("AMMWithdraw", &["Asset", "Asset2"]),
+  ("AMMVote", &["Asset", "Asset2", "TradingFee"]),
+  ("AMMBid", &["Asset", "Asset2"]),
+  ("AMMDelete", &["Asset", "Asset2"]),
+];
+
+/// Validate a transaction before serializing it.
+///
+/// The checks are:
+/// - Each `PathSet` field has at most 6 paths of at most 8 steps, see [`PathSet::validate`].
+/// - A transaction using a `TicketSequence` must set `Sequence` to 0.
+/// - A `SignerListSet` with `SignerQuorum` 0 deletes the signer list, so it must not have `SignerEntries`.
+/// - `LastLedgerSequence` must not be 0, which is always behind the current ledger.
+/// - A non-empty `SigningPubKey` must be a 33 bytes key prefixed by `ED`, `02` or `03`, see [`key_type`]. The empty
+///   `SigningPubKey` of a multi-signed transaction is allowed.
+///
+/// # Example
+///
+///```
+///use rippled_binary_codec::definition_fields::DefinitionFields;
+///use rippled_binary_codec::validate::validate_tx;
+///use serde_json::json;
+///
+///fn validate_tx_example(){
+///  let tx = json!({
+///    "TransactionType": "Payment",
+///    "Paths": [[{"currency": "XRP"}]]
+///  });
+///  assert!(validate_tx(&tx, &DefinitionFields::new()).is_ok());
+///}
+///```
+///
+/// # Errors
+///  The error of the first failed check will be returned.
+pub fn validate_tx(tx: &Value, definition_fields: &DefinitionFields) -> Result<()> {
+  let tx = tx.as_object().ok_or_else(|| DecodeError("transaction is not a JSON object".to_string()))?;
+  if tx.contains_key("TicketSequence") && tx.get("Sequence").map_or(false, |sequence| sequence != 0) {
+    return Err(ConflictingFields("Sequence".to_string(), "TicketSequence".to_string()));
+  }
+  if tx.contains_key("SignerEntries") && tx.get("SignerQuorum").map_or(false, |quorum| quorum == 0) {
+    return Err(ConflictingFields("SignerQuorum".to_string(), "SignerEntries".to_string()));
+  }
+  if tx.get("LastLedgerSequence").map_or(false, |sequence| sequence == 0) {
+    return Err(InvalidFieldValue("LastLedgerSequence".to_string()));
+  }
+  if let Some(pubkey) = tx.get("SigningPubKey").and_then(Value::as_str) {
+    if !pubkey.is_empty() && key_type(pubkey).is_none() {
+      return Err(InvalidPublicKey(pubkey.to_string()));
+    }
+  }
+  for (field_name, field_val) in tx {
+    if let Some(field) = definition_fields.get_definition_field(field_name.to_string()) {
+      if field.type_name == "PathSet" {
+        PathSet { data: field_val.clone() }.validate()?;
+      }
+    }
+  }
+  Ok(())
+}
+
+/// Check that every field to be serialized is in `allowed_fields`, including the fields nested in `STObject` and `STArray`,
+/// so that a transaction with a field which can not be displayed to the user is never signed.
+///
+/// Fields which are not serialized, like `hash`, are ignored, and so are the non-signing fields if `for_signing` is set.
+///
+/// # Example
+///
+///```
+///use rippled_binary_codec::definition_fields::DefinitionFields;
+///use rippled_binary_codec::validate::check_allowed_fields;
+///use serde_json::json;
+///use std::collections::BTreeSet;
+///
+///fn check_allowed_fields_example(){
+///  let allowed_fields: BTreeSet<String> = ["TransactionType", "Fee"].iter().map(|field| field.to_string()).collect();
+///  let tx = json!({"TransactionType": "Payment", "Fee": "12"});
+///  assert!(check_allowed_fields(&tx, &allowed_fields, true, &DefinitionFields::new()).is_ok());
+///}
+///```
+///
+/// # Errors
+///  [`DisallowedField`][`crate::errors::RippleBinaryCodecError::DisallowedField`] of the first field out of `allowed_fields` will be returned.
+pub fn check_allowed_fields(tx: &Value, allowed_fields: &BTreeSet<String>, for_signing: bool, definition_fields: &DefinitionFields) -> Result<()> {
+  let tx = tx.as_object().ok_or_else(|| DecodeError("transaction is not a JSON object".to_string()))?;
+  check_allowed_object(tx, allowed_fields, for_signing, definition_fields)
+}
+
+fn check_allowed_object(object: &Map<String, Value>, allowed_fields: &BTreeSet<String>, for_signing: bool, definition_fields: &DefinitionFields) -> Result<()> {
+  for (field_name, field_val) in object {
+    let field = definition_fields.get_definition_field(field_name.to_string());
+    if let Some(field) = &field {
+      if !field.is_serialized || (for_signing && !field.is_signing_field) {
+        continue;
+      }
+    }
+    if !allowed_fields.contains(field_name) {
+      return Err(DisallowedField(field_name.to_string()));
+    }
+    // the nested fields of an `STObject` are not filtered by `for_signing`
+    match (field.map(|field| field.type_name.as_str()), field_val) {
+      (Some("STObject"), Value::Object(inner)) => check_allowed_object(inner, allowed_fields, false, definition_fields)?,
+      (Some("STArray"), Value::Array(entries)) => {
+        for entry in entries.iter().filter_map(Value::as_object) {
+          check_allowed_object(entry, allowed_fields, false, definition_fields)?;
+        }
+      },
+      _ => {},
+    }
+  }
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use serde_json::json;
+  use super::*;
+
+  #[test]
+  fn test_validate_tx_ticket_sequence() {
+    let definition_fields = DefinitionFields::new();
+    let tx = json!({"TransactionType": "AccountSet", "Sequence": 7});
+    assert_eq!(validate_tx(&tx, &definition_fields), Ok(()));
+
+    let tx = json!({"TransactionType": "AccountSet", "Sequence": 0, "TicketSequence": 7});
+    assert_eq!(validate_tx(&tx, &definition_fields), Ok(()));
+
+    let tx = json!({"TransactionType": "AccountSet", "Sequence": 6, "TicketSequence": 7});
+    assert_eq!(validate_tx(&tx, &definition_fields), Err(ConflictingFields("Sequence".to_string(), "TicketSequence".to_string())));
+  }
+
+  #[test]
+  fn test_validate_tx_signer_quorum() {
+    let definition_fields = DefinitionFields::new();
+    let signer_entries = json!([{"SignerEntry": {"Account": "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B", "SignerWeight": 1}}]);
+    let tx = json!({"TransactionType": "SignerListSet", "SignerQuorum": 0});
+    assert_eq!(validate_tx(&tx, &definition_fields), Ok(()));
+
+    let tx = json!({"TransactionType": "SignerListSet", "SignerQuorum": 1, "SignerEntries": signer_entries});
+    assert_eq!(validate_tx(&tx, &definition_fields), Ok(()));
+
+    let tx = json!({"TransactionType": "SignerListSet", "SignerQuorum": 0, "SignerEntries": signer_entries});
+    assert_eq!(validate_tx(&tx, &definition_fields), Err(ConflictingFields("SignerQuorum".to_string(), "SignerEntries".to_string())));
+  }
+
+  #[test]
+  fn test_validate_tx_last_ledger_sequence() {
+    let definition_fields = DefinitionFields::new();
+    let tx = json!({"TransactionType": "AccountSet", "LastLedgerSequence": 80410003});
+    assert_eq!(validate_tx(&tx, &definition_fields), Ok(()));
+
+    let tx = json!({"TransactionType": "AccountSet", "LastLedgerSequence": 0});
+    assert_eq!(validate_tx(&tx, &definition_fields), Err(InvalidFieldValue("LastLedgerSequence".to_string())));
+  }
+
+  #[test]
+  fn test_validate_tx_signing_pub_key() {
+    let definition_fields = DefinitionFields::new();
+    for pubkey in ["", "03EE83BB432547885C219634A1BC407A9DB0474145D69737D09CCDC63E1DEE7FE3", "ED5F5AC8B98974A3CA843326D9B88CEBD0560177B973EE0B149F782CFAA06DC66A"] {
+      let tx = json!({"TransactionType": "AccountSet", "SigningPubKey": pubkey});
+      assert_eq!(validate_tx(&tx, &definition_fields), Ok(()));
+    }
+    // without the prefix, truncated, and with an unknown prefix
+    for pubkey in ["EE83BB432547885C219634A1BC407A9DB0474145D69737D09CCDC63E1DEE7FE3", "03EE83BB432547885C219634A1BC407A9DB0474145D69737D09CCDC63E1DEE7F", "04EE83BB432547885C219634A1BC407A9DB0474145D69737D09CCDC63E1DEE7FE3"] {
+      let tx = json!({"TransactionType": "AccountSet", "SigningPubKey": pubkey});
+      assert_eq!(validate_tx(&tx, &definition_fields), Err(InvalidPublicKey(pubkey.to_string())));
+    }
+  }
+
+  #[test]
+  fn test_check_allowed_fields() {
+    let definition_fields = DefinitionFields::new();
+    let allowed_fields: BTreeSet<String> = ["TransactionType", "Amount", "Fee", "Memos", "Memo", "MemoData"]
+      .iter().map(|field_name| field_name.to_string()).collect();
+    // the keys of an issued amount are not fields, `TxnSignature` and `hash` are not signed
+    let tx = json!({
+      "TransactionType": "Payment",
+      "Amount": {"currency": "USD", "issuer": "rvYAfWj5gh67oV6fW32ZzP3Aw4Eubs59B", "value": "1"},
+      "Memos": [{"Memo": {"MemoData": "72656E74"}}],
+      "TxnSignature": "3044",
+      "hash": "73734B611DDA23D3F5F62E20A173B78AB8406AC5015094DA53F53D39B9EDB06C"
+    });
+    assert_eq!(check_allowed_fields(&tx, &allowed_fields, true, &definition_fields), Ok(()));
+    assert_eq!(check_allowed_fields(&tx, &allowed_fields, false, &definition_fields), Err(DisallowedField("TxnSignature".to_string())));
+
+    let tx = json!({"TransactionType": "Payment", "Memos": [{"Memo": {"MemoType": "636C69656E74"}}]});
+    assert_eq!(check_allowed_fields(&tx, &allowed_fields, true, &definition_fields), Err(DisallowedField("MemoType".to_string())));
+    let tx = json!({"TransactionType": "Payment", "Comment": "rent"});
+    assert_eq!(check_allowed_fields(&tx, &allowed_fields, true, &definition_fields), Err(DisallowedField("Comment".to_string())));
+  }
+}